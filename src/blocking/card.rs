@@ -0,0 +1,43 @@
+//! [`card`](crate::card)的阻塞式接口
+
+use crate::{
+    blocking::block_on,
+    card::{Bill, Card, EnergyFees},
+    errors::card::CardResult,
+    session::{Client, Session},
+};
+
+/// [`card::access_card`](crate::card::access_card)的阻塞版本
+pub fn access_card(client: &Client, session: &mut Session) -> CardResult<()> {
+    block_on(crate::card::access_card(client, session))
+}
+
+/// [`Card::fetch_self`]的阻塞版本
+pub fn fetch_card(client: &Client, session: &Session) -> CardResult<Card> {
+    block_on(Card::fetch_self(client, session))
+}
+
+/// [`Card::fetch_bill`]的阻塞版本
+///
+/// *`start_date`, `end_date`日期格式应当符合`%Y-%m-%d`*
+pub fn fetch_bill(
+    card: &Card,
+    client: &Client,
+    session: &Session,
+    start_date: impl AsRef<str>,
+    end_date: impl AsRef<str>,
+    page: u16,
+    row: u16,
+) -> CardResult<Vec<Bill>> {
+    block_on(card.fetch_bill(client, session, start_date, end_date, page, row))
+}
+
+/// [`EnergyFees::fetch_self`]的阻塞版本
+pub fn fetch_energy_fees(
+    client: &Client,
+    session: &mut Session,
+    room: impl AsRef<str>,
+    is_huxi: bool,
+) -> CardResult<EnergyFees> {
+    block_on(EnergyFees::fetch_self(client, session, room, is_huxi))
+}
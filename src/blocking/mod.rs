@@ -0,0 +1,51 @@
+//! 阻塞式接口
+//!
+//! 该模块以阻塞调用的形式镜像了[`rsmycqu`](crate)的全部异步接口，与异步接口共享相同的数据模型、
+//! [`Client`](crate::session::Client)与[`Session`](crate::session::Session)，无需调用方自行创建异步运行时
+//!
+//! 所有阻塞接口均在库内部维护的运行时中执行，**请勿在异步上下文中调用**（这会导致panic），
+//! 这一限制与[`reqwest::blocking`](https://docs.rs/reqwest/latest/reqwest/blocking/index.html)相同
+//!
+//! # Examples
+//! ```rust, no_run
+//! use rsmycqu::{blocking, session::{Client, Session}};
+//!
+//! let client = Client::default();
+//! let mut session = Session::new();
+//! blocking::sso::login(&client, &mut session, "your_auth", "your_password", false).unwrap();
+//! blocking::mycqu::access_mycqu(&client, &mut session).unwrap();
+//! let user = blocking::mycqu::fetch_user(&client, &session).unwrap();
+//! ```
+
+use std::{future::Future, sync::LazyLock};
+
+use tokio::runtime::{Builder, Runtime};
+
+#[cfg(feature = "card")]
+pub mod card;
+#[cfg(feature = "mycqu")]
+pub mod mycqu;
+#[cfg(feature = "sso")]
+pub mod sso;
+
+#[cfg(test)]
+mod tests;
+
+static RUNTIME: LazyLock<Runtime> = LazyLock::new(|| {
+    Builder::new_multi_thread()
+        .worker_threads(1)
+        .thread_name("rsmycqu-blocking")
+        .enable_all()
+        .build()
+        .expect("failed to build the blocking runtime")
+});
+
+/// 在库内部维护的运行时中阻塞执行`future`直至完成
+///
+/// 可用于以阻塞方式调用尚未在本模块中镜像的异步接口
+///
+/// # Panics
+/// 在异步上下文中调用时panic
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    RUNTIME.block_on(future)
+}
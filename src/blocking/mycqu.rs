@@ -0,0 +1,188 @@
+//! [`mycqu`](crate::mycqu)的阻塞式接口
+
+use crate::{
+    blocking::block_on,
+    errors::mycqu::MyCQUResult,
+    mycqu::User,
+    session::{Client, Session},
+};
+
+/// [`mycqu::access_mycqu`](crate::mycqu::access_mycqu)的阻塞版本
+pub fn access_mycqu(client: &Client, session: &mut Session) -> MyCQUResult<()> {
+    block_on(crate::mycqu::access_mycqu(client, session))
+}
+
+/// [`mycqu::refresh_mycqu`](crate::mycqu::refresh_mycqu)的阻塞版本
+pub fn refresh_mycqu(client: &Client, session: &mut Session) -> MyCQUResult<()> {
+    block_on(crate::mycqu::refresh_mycqu(client, session))
+}
+
+/// [`User::fetch_self`]的阻塞版本
+pub fn fetch_user(client: &Client, session: &Session) -> MyCQUResult<User> {
+    block_on(User::fetch_self(client, session))
+}
+
+pub mod course {
+    //! [`mycqu::course`](crate::mycqu::course)的阻塞式接口
+
+    use crate::{
+        blocking::block_on,
+        errors::mycqu::MyCQUResult,
+        mycqu::course::{CQUSession, CQUSessionInfo, CourseTimetable},
+        session::{Client, Session},
+    };
+
+    /// [`CQUSession::fetch_all`]的阻塞版本
+    pub fn fetch_all_sessions(client: &Client, session: &Session) -> MyCQUResult<Vec<CQUSession>> {
+        block_on(CQUSession::fetch_all(client, session))
+    }
+
+    /// [`CQUSessionInfo::fetch_all`]的阻塞版本
+    pub fn fetch_all_session_infos(
+        client: &Client,
+        session: &Session,
+    ) -> MyCQUResult<Vec<CQUSessionInfo>> {
+        block_on(CQUSessionInfo::fetch_all(client, session))
+    }
+
+    /// [`CQUSessionInfo::fetch_curr`]的阻塞版本
+    pub fn fetch_curr_session_info(
+        client: &Client,
+        session: &Session,
+    ) -> MyCQUResult<CQUSessionInfo> {
+        block_on(CQUSessionInfo::fetch_curr(client, session))
+    }
+
+    /// [`CQUSessionInfo::fetch_detail`]的阻塞版本
+    pub fn fetch_session_info_detail(
+        client: &Client,
+        session: &Session,
+        session_id: u32,
+    ) -> MyCQUResult<CQUSessionInfo> {
+        block_on(CQUSessionInfo::fetch_detail(client, session, session_id))
+    }
+
+    /// [`CourseTimetable::fetch_curr`]的阻塞版本
+    pub fn fetch_curr_timetables(
+        client: &Client,
+        session: &Session,
+        student_id: impl AsRef<str>,
+        cqu_session_id: u16,
+    ) -> MyCQUResult<Vec<CourseTimetable>> {
+        block_on(CourseTimetable::fetch_curr(
+            client,
+            session,
+            student_id,
+            cqu_session_id,
+        ))
+    }
+
+    /// [`CourseTimetable::fetch_enroll`]的阻塞版本
+    pub fn fetch_enroll_timetables(
+        client: &Client,
+        session: &Session,
+        student_id: impl AsRef<str>,
+    ) -> MyCQUResult<Vec<CourseTimetable>> {
+        block_on(CourseTimetable::fetch_enroll(client, session, student_id))
+    }
+}
+
+pub mod enroll {
+    //! [`mycqu::enroll`](crate::mycqu::enroll)的阻塞式接口
+
+    use std::collections::HashMap;
+
+    use crate::{
+        blocking::block_on,
+        errors::mycqu::MyCQUResult,
+        mycqu::enroll::{EnrollCourseInfo, EnrollCourseItem},
+        session::{Client, Session},
+    };
+
+    /// [`EnrollCourseInfo::fetch_all`]的阻塞版本
+    pub fn fetch_enroll_course_infos(
+        client: &Client,
+        session: &Session,
+        is_major: bool,
+    ) -> MyCQUResult<HashMap<String, Vec<EnrollCourseInfo>>> {
+        block_on(EnrollCourseInfo::fetch_all(client, session, is_major))
+    }
+
+    /// [`EnrollCourseItem::fetch_all`]的阻塞版本
+    pub fn fetch_enroll_course_items(
+        client: &Client,
+        session: &Session,
+        course_id: impl AsRef<str>,
+        is_major: bool,
+    ) -> MyCQUResult<Vec<EnrollCourseItem>> {
+        block_on(EnrollCourseItem::fetch_all(
+            client, session, course_id, is_major,
+        ))
+    }
+}
+
+pub mod exam {
+    //! [`mycqu::exam`](crate::mycqu::exam)的阻塞式接口
+
+    use crate::{
+        blocking::block_on,
+        errors::mycqu::MyCQUResult,
+        mycqu::exam::Exam,
+        session::{Client, Session},
+    };
+
+    /// [`Exam::fetch_all`]的阻塞版本
+    pub fn fetch_exams(
+        client: &Client,
+        session: &Session,
+        student_id: impl AsRef<str>,
+    ) -> MyCQUResult<Vec<Exam>> {
+        block_on(Exam::fetch_all(client, session, student_id))
+    }
+}
+
+pub mod score {
+    //! [`mycqu::score`](crate::mycqu::score)的阻塞式接口
+
+    use crate::{
+        blocking::block_on,
+        errors::mycqu::MyCQUResult,
+        mycqu::score::{GPARanking, GPARankingReport, Score, ScoreDetail},
+        session::{Client, Session},
+    };
+
+    /// [`Score::fetch_self`]的阻塞版本
+    pub fn fetch_scores(
+        client: &Client,
+        session: &Session,
+        is_minor: bool,
+    ) -> MyCQUResult<Vec<Score>> {
+        block_on(Score::fetch_self(client, session, is_minor))
+    }
+
+    /// [`Score::fetch_detail`]的阻塞版本
+    pub fn fetch_score_detail(
+        score: &Score,
+        client: &Client,
+        session: &Session,
+    ) -> MyCQUResult<ScoreDetail> {
+        block_on(score.fetch_detail(client, session))
+    }
+
+    /// [`GPARanking::fetch_self`]的阻塞版本
+    pub fn fetch_gpa_ranking(
+        client: &Client,
+        session: &Session,
+        is_minor: bool,
+    ) -> MyCQUResult<GPARanking> {
+        block_on(GPARanking::fetch_self(client, session, is_minor))
+    }
+
+    /// [`GPARankingReport::fetch_self`]的阻塞版本
+    pub fn fetch_gpa_ranking_report(
+        client: &Client,
+        session: &Session,
+    ) -> MyCQUResult<GPARankingReport> {
+        block_on(GPARankingReport::fetch_self(client, session))
+    }
+}
@@ -0,0 +1,89 @@
+//! [`sso`](crate::sso)的阻塞式接口
+
+use crate::{
+    blocking::block_on,
+    errors::sso::SSOResult,
+    session::{Client, Session},
+    sso::{
+        CaptchaChallenge, LoginResult, QrLogin, QrLoginStart, QrLoginStatus, ServiceAccess,
+        cas::CasUser,
+    },
+};
+
+/// [`sso::login`](crate::sso::login)的阻塞版本
+pub fn login(
+    client: &Client,
+    session: &mut Session,
+    auth: impl AsRef<str>,
+    password: impl AsRef<str>,
+    force_relogin: bool,
+) -> SSOResult<LoginResult> {
+    block_on(crate::sso::login(
+        client,
+        session,
+        auth,
+        password,
+        force_relogin,
+    ))
+}
+
+/// [`sso::access_all`](crate::sso::access_all)的阻塞版本
+#[cfg(any(feature = "mycqu", feature = "card"))]
+pub fn access_all(client: &Client, session: &mut Session) -> crate::sso::AccessReport {
+    block_on(crate::sso::access_all(client, session))
+}
+
+/// [`sso::logout`](crate::sso::logout)的阻塞版本
+pub fn logout(client: &Client, session: &mut Session) -> SSOResult<()> {
+    block_on(crate::sso::logout(client, session))
+}
+
+/// [`sso::login_with_captcha`](crate::sso::login_with_captcha)的阻塞版本
+pub fn login_with_captcha(
+    client: &Client,
+    session: &mut Session,
+    challenge: CaptchaChallenge,
+    auth: impl AsRef<str>,
+    password: impl AsRef<str>,
+    captcha: impl AsRef<str>,
+) -> SSOResult<LoginResult> {
+    block_on(crate::sso::login_with_captcha(
+        client, session, challenge, auth, password, captcha,
+    ))
+}
+
+/// [`sso::start_qr_login`](crate::sso::start_qr_login)的阻塞版本
+pub fn start_qr_login(
+    client: &Client,
+    session: &mut Session,
+    force_relogin: bool,
+) -> SSOResult<QrLoginStart> {
+    block_on(crate::sso::start_qr_login(client, session, force_relogin))
+}
+
+/// [`sso::poll_qr_login`](crate::sso::poll_qr_login)的阻塞版本
+pub fn poll_qr_login(
+    client: &Client,
+    session: &mut Session,
+    qr_login: &QrLogin,
+) -> SSOResult<QrLoginStatus> {
+    block_on(crate::sso::poll_qr_login(client, session, qr_login))
+}
+
+/// [`sso::access_service`](crate::sso::access_service)的阻塞版本
+pub fn access_service(
+    client: &Client,
+    session: &Session,
+    service: impl AsRef<str>,
+) -> SSOResult<ServiceAccess> {
+    block_on(crate::sso::access_service(client, session, service))
+}
+
+/// [`sso::cas::validate_ticket`](crate::sso::cas::validate_ticket)的阻塞版本
+pub fn validate_ticket(
+    client: &Client,
+    service: impl AsRef<str>,
+    ticket: impl AsRef<str>,
+) -> SSOResult<CasUser> {
+    block_on(crate::sso::cas::validate_ticket(client, service, ticket))
+}
@@ -0,0 +1,33 @@
+use rstest::*;
+
+use crate::{
+    blocking::{block_on, mycqu},
+    errors::ApiError,
+    session::{Client, Session},
+};
+
+#[rstest]
+fn test_block_on_outside_runtime() {
+    assert_eq!(block_on(async { 1 + 1 }), 2);
+}
+
+#[rstest]
+fn test_blocking_fetch_before_access() {
+    let client = Client::default();
+    let session = Session::new();
+
+    let res = mycqu::fetch_user(&client, &session);
+    assert!(matches!(res.unwrap_err(), ApiError::NotAccess));
+
+    let res = mycqu::score::fetch_scores(&client, &session, false);
+    assert!(matches!(res.unwrap_err(), ApiError::NotAccess));
+}
+
+#[rstest]
+fn test_blocking_access_before_login() {
+    let client = Client::default();
+    let mut session = Session::new();
+
+    let res = mycqu::access_mycqu(&client, &mut session);
+    assert!(matches!(res.unwrap_err(), ApiError::NotLogin));
+}
@@ -13,7 +13,7 @@ use crate::{
     session::{Client, Session},
     utils::{
         ApiModel,
-        consts::{CARD_GET_BILL_URL, CARD_GET_CARD_URL},
+        consts::{CARD_GET_BILL_PATH, CARD_GET_CARD_PATH},
         response_json_map,
     },
 };
@@ -83,7 +83,9 @@ impl Card {
     /// ```
     pub async fn fetch_self(client: &Client, session: &Session) -> CardResult<Card> {
         let res = card_request_handler(client, session, |client| {
-            client.post(CARD_GET_CARD_URL).header(CONTENT_LENGTH, 0)
+            client
+                .post(client.endpoints().card_url(CARD_GET_CARD_PATH))
+                .header(CONTENT_LENGTH, 0)
         })
         .await?;
 
@@ -173,13 +175,15 @@ impl Card {
         row: u16,
     ) -> CardResult<Vec<Bill>> {
         let res = card_request_handler(client, session, |client| {
-            client.post(CARD_GET_BILL_URL).form(&[
-                ("sdate", start_date.as_ref()),
-                ("edate", end_date.as_ref()),
-                ("account", self.id.to_string().as_ref()),
-                ("page", &page.to_string()),
-                ("row", &row.to_string()),
-            ])
+            client
+                .post(client.endpoints().card_url(CARD_GET_BILL_PATH))
+                .form(&[
+                    ("sdate", start_date.as_ref()),
+                    ("edate", end_date.as_ref()),
+                    ("account", self.id.to_string().as_ref()),
+                    ("page", &page.to_string()),
+                    ("row", &row.to_string()),
+                ])
         })
         .await?;
 
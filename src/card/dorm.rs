@@ -1,6 +1,6 @@
 //! 宿舍水电费相关API
 use reqwest::{
-    StatusCode,
+    Response, StatusCode,
     header::{COOKIE, HeaderValue},
 };
 use serde::{Deserialize, Serialize};
@@ -8,15 +8,15 @@ use serde_json::{Map, Value};
 use snafu::ensure;
 
 use crate::{
-    card::utils::card_request_handler,
+    card::utils::{card_request, card_request_handler},
     errors,
     errors::{ApiError, card::CardResult},
-    session::{Client, Session},
+    session::{Client, Session, access_info::Service, reauth::reauthenticate},
     utils::{
         ApiModel,
         consts::{
-            CARD_BLADE_AUTH_URL, CARD_GET_DORM_FEE_URL, CARD_PAGE_TICKET_POST_FORM_URL,
-            CARD_PAGE_URL,
+            CARD_BLADE_AUTH_PATH, CARD_GET_DORM_FEE_PATH, CARD_PAGE_PATH,
+            CARD_PAGE_TICKET_POST_FORM_PATH,
         },
         response_json_map,
     },
@@ -24,10 +24,14 @@ use crate::{
 
 async fn get_page_ticket(client: &Client, session: &Session) -> CardResult<String> {
     let res = card_request_handler(client, session, |client| {
-        client.post(CARD_PAGE_URL).form(&[
+        let endpoints = client.endpoints();
+        client.post(endpoints.card_url(CARD_PAGE_PATH)).form(&[
             ("EMenuName", "电费、网费"),
             ("MenuName", "电费、网费"),
-            ("Url", CARD_PAGE_TICKET_POST_FORM_URL),
+            (
+                "Url",
+                &endpoints.card_blade_url(CARD_PAGE_TICKET_POST_FORM_PATH),
+            ),
             ("apptype", "4"),
             ("flowID", "10002"),
         ])
@@ -58,7 +62,7 @@ async fn get_synjones_auth(
 ) -> CardResult<String> {
     let res = card_request_handler(client, session, |client| {
         client
-            .post(CARD_BLADE_AUTH_URL)
+            .post(client.endpoints().card_blade_url(CARD_BLADE_AUTH_PATH))
             .form(&[("ticket", ticket.as_ref()), ("json", "true")])
     })
     .await?;
@@ -145,50 +149,22 @@ impl EnergyFees {
         room: impl AsRef<str>,
         is_huxi: bool,
     ) -> CardResult<EnergyFees> {
-        let card_access_info = session
-            .access_infos
-            .card_access_info
-            .as_ref()
-            .ok_or(ApiError::NotAccess)?;
-
-        if card_access_info.synjones_auth.is_none() {
-            let ticket = get_page_ticket(client, session).await?;
-            let synjones_auth = get_synjones_auth(client, session, ticket).await?;
-            session
-                .access_infos
-                .card_access_info
-                .as_mut()
-                .unwrap()
-                .synjones_auth
-                .replace(synjones_auth);
-        }
-
-        let synjones_auth = session
-            .access_infos
-            .card_access_info
-            .as_ref()
-            .unwrap()
-            .synjones_auth
-            .as_ref()
-            .unwrap();
-        let cookie_header = HeaderValue::from_str(&format!("synjones-auth={}", synjones_auth))
-            .map_err(|err| ApiError::Website {
-                msg: format!("Set cookies error: {}", err),
-            })?;
+        ensure!(
+            session.access_infos.read().card_access_info.is_some(),
+            errors::NotAccessSnafu
+        );
 
-        let res = card_request_handler(client, session, |client| {
-            client
-                .post(CARD_GET_DORM_FEE_URL)
-                .form(&[
-                    ("feeitemid", if is_huxi { "182" } else { "181" }), // 虎溪校区该项为'182'，老校区为'181'
-                    ("json", "true"),
-                    ("level", "2"),
-                    ("room", room.as_ref()),
-                    ("type", "IEC"),
-                ])
-                .header(COOKIE, cookie_header)
-        })
-        .await?;
+        let res = match dorm_fee_request(client, session, room.as_ref(), is_huxi).await {
+            // 访问权限失效时，若启用了自动重新认证则重新获取权限并写回会话，随后重新获取synjones-auth再请求一次
+            Err(ApiError::NotAccess) => {
+                let fresh = reauthenticate(client, session, Service::Card)
+                    .await
+                    .ok_or(ApiError::NotAccess)?;
+                session.access_infos = fresh.access_infos;
+                dorm_fee_request(client, session, room.as_ref(), is_huxi).await?
+            }
+            res => res?,
+        };
 
         ensure!(
             res.status() == StatusCode::OK,
@@ -225,3 +201,54 @@ impl EnergyFees {
             })
     }
 }
+
+/// 使用会话中保存的synjones-auth请求宿舍水电费，会话中没有时先获取并保存
+///
+/// 该请求不会自动重新认证，synjones-auth失效时返回[`ApiError::NotAccess`]
+async fn dorm_fee_request(
+    client: &Client,
+    session: &mut Session,
+    room: &str,
+    is_huxi: bool,
+) -> CardResult<Response> {
+    let synjones_auth = session
+        .access_infos
+        .read()
+        .card_access_info
+        .as_ref()
+        .ok_or(ApiError::NotAccess)?
+        .synjones_auth
+        .clone();
+
+    let synjones_auth = match synjones_auth {
+        Some(synjones_auth) => synjones_auth,
+        None => {
+            let ticket = get_page_ticket(client, session).await?;
+            let synjones_auth = get_synjones_auth(client, session, ticket).await?;
+            if let Some(card_access_info) = session.access_infos.get_mut().card_access_info.as_mut()
+            {
+                card_access_info.synjones_auth = Some(synjones_auth.clone());
+            }
+            synjones_auth
+        }
+    };
+    let cookie_header = HeaderValue::from_str(&format!("synjones-auth={}", synjones_auth))
+        .map_err(|err| ApiError::Website {
+            msg: format!("Set cookies error: {}", err),
+        })?;
+
+    card_request(
+        session,
+        client
+            .post(client.endpoints().card_blade_url(CARD_GET_DORM_FEE_PATH))
+            .form(&[
+                ("feeitemid", if is_huxi { "182" } else { "181" }), // 虎溪校区该项为'182'，老校区为'181'
+                ("json", "true"),
+                ("level", "2"),
+                ("room", room),
+                ("type", "IEC"),
+            ])
+            .header(COOKIE, cookie_header),
+    )
+    .await
+}
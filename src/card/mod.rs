@@ -13,7 +13,7 @@ use crate::{
     session::{Client, Session, access_info::CardAccessInfo},
     sso::access_services,
     utils::{
-        consts::{CARD_HALL_TICKET_URL, CARD_SERVICE_URL},
+        consts::{CARD_HALL_TICKET_PATH, CARD_SERVICE_PATH},
         get_response_header,
         page_parser::card_access_parser,
     },
@@ -32,7 +32,12 @@ pub async fn access_card(client: &Client, session: &mut Session) -> CardResult<(
     ensure!(session.is_login, errors::NotLoginSnafu);
 
     let res = whatever!(
-        access_services(client, session, CARD_SERVICE_URL).await,
+        access_services(
+            client,
+            session,
+            client.endpoints().card_ias_url(CARD_SERVICE_PATH)
+        )
+        .await,
         "Unexpected SSOError happened"
     );
 
@@ -44,11 +49,12 @@ pub async fn access_card(client: &Client, session: &mut Session) -> CardResult<(
     let sso_ticket_id = card_access_parser(res.text().await?)
         .whatever_context::<&str, ApiError<CardError>>("Unable to parse card page")?;
 
+    let hall_ticket_url = client.endpoints().card_url(CARD_HALL_TICKET_PATH);
     let res = session
-        .execute(client.post(CARD_HALL_TICKET_URL).form(&[
+        .execute(client.post(&hall_ticket_url).form(&[
             ("errorcode", "1"),
             ("ssoticketid", &sso_ticket_id),
-            ("continueurl", CARD_HALL_TICKET_URL),
+            ("continueurl", &hall_ticket_url),
         ]))
         .await?;
 
@@ -57,9 +63,7 @@ pub async fn access_card(client: &Client, session: &mut Session) -> CardResult<(
         errors::card::AccessSnafu
     );
 
-    session.access_infos.card_access_info = Some(CardAccessInfo {
-        synjones_auth: None,
-    });
+    session.access_infos.get_mut().card_access_info = Some(CardAccessInfo::new());
 
     Ok(())
 }
@@ -4,10 +4,10 @@ use rstest::*;
 use serde_json::json;
 
 use crate::{
-    card::{Card, EnergyFees, access_card},
+    card::{Card, EnergyFees, Subsidy, access_card},
     errors::ApiError,
     session::Session,
-    utils::test_fixture::{access_card_session, login_session, shared_client},
+    utils::test_fixture::{access_card_session, login_session, replay_session, shared_client},
 };
 
 #[rstest]
@@ -130,3 +130,46 @@ async fn test_fetch_bills(
         .await
         .unwrap();
 }
+
+#[rstest]
+#[tokio::test]
+async fn test_replay_card_and_bill(shared_client: &'static crate::session::Client) {
+    let session = replay_session(include_str!("cassettes/card.json"));
+
+    let card = Card::fetch_self(shared_client, &session).await.unwrap();
+    assert_eq!(card.id, 100001);
+    assert_eq!(card.amount, 12345);
+
+    let bills = card
+        .fetch_bill(shared_client, &session, "2023-11-10", "2023-12-12", 1, 100)
+        .await
+        .unwrap();
+    assert_eq!(bills.len(), 1);
+    assert_eq!(bills[0].tran_amount, -1200);
+    assert_eq!(bills[0].acc_amount, 11145);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_replay_energy_fees(shared_client: &'static crate::session::Client) {
+    let mut session = replay_session(include_str!("cassettes/energy_fees.json"));
+
+    let fees = EnergyFees::fetch_self(shared_client, &mut session, "b5321", true)
+        .await
+        .unwrap();
+    assert_eq!(fees.balance, "45.12");
+    assert_eq!(
+        fees.subsidies,
+        Subsidy::Huxi {
+            electricity: "10.5".to_string(),
+            water: "2.0".to_string(),
+        }
+    );
+    assert!(
+        session
+            .access_infos()
+            .card_access_info
+            .as_ref()
+            .is_some_and(|info| info.synjones_auth.is_some())
+    );
+}
@@ -1,8 +1,10 @@
 use reqwest::{RequestBuilder, Response, StatusCode};
+use snafu::ensure;
 
 use crate::{
+    errors,
     errors::{ApiError, card::CardResult},
-    session::{Client, Session},
+    session::{Client, Session, access_info::Service, reauth::reauthenticate},
 };
 
 pub(super) async fn card_request_handler<T>(
@@ -11,16 +13,36 @@ pub(super) async fn card_request_handler<T>(
     f: T,
 ) -> CardResult<Response>
 where
-    T: FnOnce(&Client) -> RequestBuilder,
+    T: Fn(&Client) -> RequestBuilder,
 {
-    if session.access_infos.card_access_info.is_none() {
-        return Err(ApiError::NotAccess);
-    }
-
-    let res = session.execute(f(client)).await?;
+    ensure!(
+        session.access_infos.read().card_access_info.is_some(),
+        errors::NotAccessSnafu
+    );
 
-    if res.status() == StatusCode::UNAUTHORIZED {
-        return Err(ApiError::NotAccess);
+    match card_request(session, f(client)).await {
+        // 访问权限失效时，若启用了自动重新认证则重新获取权限并写回会话，随后重新构建请求发送一次
+        Err(ApiError::NotAccess) => {
+            let mut fresh = reauthenticate(client, session, Service::Card)
+                .await
+                .ok_or(ApiError::NotAccess)?;
+            session.access_infos.write().card_access_info =
+                fresh.access_infos.get_mut().card_access_info.take();
+            card_request(session, f(client)).await
+        }
+        res => res,
     }
+}
+
+/// 发送一次请求，收到`401`响应时返回[`ApiError::NotAccess`]
+pub(super) async fn card_request(
+    session: &Session,
+    builder: RequestBuilder,
+) -> CardResult<Response> {
+    let res = session.execute(builder).await?;
+    ensure!(
+        res.status() != StatusCode::UNAUTHORIZED,
+        errors::NotAccessSnafu
+    );
     Ok(res)
 }
@@ -0,0 +1,360 @@
+//! 自动登陆并获取服务访问权限的高层客户端
+//!
+//! 底层接口要求调用者自行传递[`Client`]与[`Session`]，并在调用前依次完成[`login`]与
+//! [`access_mycqu`](crate::mycqu::access_mycqu)、[`access_card`](crate::card::access_card)等权限获取。
+//! [`CquClient`]持有二者及登陆凭据，在第一次调用某个服务的接口时自动登陆统一身份认证并获取该服务的访问权限，
+//! 之后的调用直接复用已获取的权限；访问权限失效时重新登陆并获取权限，新的访问信息保存在[`CquClient`]中供之后的调用使用
+//!
+//! 底层接口仍然可用，[`CquClient::session`]返回的会话与[`CquClient`]共享Cookie，并拥有调用时[`CquClient`]的访问信息
+//!
+//! # Examples
+//! ```rust, no_run
+//! # use rsmycqu::CquClient;
+//! # async fn facade() {
+//! let client = CquClient::new("your_auth", "your_password");
+//! let scores = client.scores(false).await.unwrap();
+//! let exams = client.exams().await.unwrap();
+//! # }
+//! ```
+
+use tokio::sync::Mutex;
+#[cfg(any(feature = "mycqu", feature = "card"))]
+use tokio::sync::OnceCell;
+
+#[cfg(feature = "card")]
+use crate::card::{Bill, Card, EnergyFees, access_card};
+#[cfg(feature = "mycqu")]
+use crate::mycqu::{
+    User, access_mycqu,
+    course::{CQUSessionInfo, CourseTimetable},
+    exam::Exam,
+    score::{GPARanking, GPARankingReport, Score, ScoreDetail, ScoreWatcher},
+};
+use crate::{
+    errors::client::{ClientError, ClientResult},
+    session::{Client, Session, reauth::Credentials},
+    sso::{LoginResult, login, logout},
+};
+#[cfg(any(feature = "mycqu", feature = "card"))]
+use crate::{
+    errors::{ApiError, ApiResult, RsMyCQUError},
+    session::access_info::Service,
+    sso::{AccessReport, access_all},
+};
+
+/// 自动登陆并获取服务访问权限的高层客户端
+#[derive(Debug)]
+pub struct CquClient {
+    client: Client,
+    credentials: Credentials,
+    /// 登陆与获取访问权限期间持有，避免并发调用时重复登陆
+    session: Mutex<Session>,
+    /// 当前登陆用户的信息，用于需要学号的接口
+    #[cfg(feature = "mycqu")]
+    user: OnceCell<User>,
+    /// 当前登陆用户的校园卡信息，用于查询账单
+    #[cfg(feature = "card")]
+    card: OnceCell<Card>,
+}
+
+impl CquClient {
+    /// 使用统一身份认证号与密码创建客户端，创建时不会发起任何请求
+    ///
+    /// 客户端使用的会话启用了以该账号密码进行的[自动重新认证](crate::session::reauth)
+    pub fn new(auth: impl Into<String>, password: impl Into<String>) -> Self {
+        let credentials = Credentials::new(auth, password);
+        CquClient {
+            client: Client::default(),
+            session: Mutex::new(Session::new().set_credential_provider(credentials.clone())),
+            credentials,
+            #[cfg(feature = "mycqu")]
+            user: OnceCell::new(),
+            #[cfg(feature = "card")]
+            card: OnceCell::new(),
+        }
+    }
+
+    /// 设置发起请求使用的[`Client`]，如指向模拟服务器的[`Client`]
+    pub fn set_client(self, client: Client) -> Self {
+        CquClient { client, ..self }
+    }
+
+    /// 设置使用的会话，如由[`Session::restore`]恢复或从[`SessionPool`](crate::session::pool::SessionPool)中取出的会话
+    ///
+    /// 会话已登陆或已获取服务访问权限时直接复用，会话将启用以该客户端账号密码进行的自动重新认证
+    pub fn set_session(self, session: Session) -> Self {
+        CquClient {
+            session: Mutex::new(session.set_credential_provider(self.credentials.clone())),
+            ..self
+        }
+    }
+
+    /// 发起请求使用的[`Client`]
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    /// 与该客户端共享Cookie、拥有该客户端当前访问信息的会话，可用于调用底层接口
+    pub async fn session(&self) -> Session {
+        self.session.lock().await.clone()
+    }
+
+    /// 登陆统一身份认证，已登陆时直接返回
+    ///
+    /// 统一身份认证要求验证码等登陆失败的情况返回[`ClientError::LoginFailed`]，
+    /// 此时可通过[`login_with_captcha`](crate::sso::login_with_captcha)完成登陆后再通过[`CquClient::set_session`]设置会话
+    pub async fn login(&self) -> ClientResult<()> {
+        let mut session = self.session.lock().await;
+        self.ensure_login(&mut session).await
+    }
+
+    /// 登出统一身份认证
+    pub async fn logout(&self) -> ClientResult<()> {
+        let mut session = self.session.lock().await;
+        logout(&self.client, &mut session).await?;
+        Ok(())
+    }
+
+    async fn ensure_login(&self, session: &mut Session) -> ClientResult<()> {
+        if session.is_login() {
+            return Ok(());
+        }
+
+        match login(
+            &self.client,
+            session,
+            &self.credentials.auth,
+            &self.credentials.password,
+            false,
+        )
+        .await?
+        {
+            LoginResult::Success => Ok(()),
+            result => Err(ClientError::LoginFailed { result }),
+        }
+    }
+
+    /// 登陆统一身份认证后并发获取全部服务的访问权限，见[`access_all`](crate::sso::access_all)
+    #[cfg(any(feature = "mycqu", feature = "card"))]
+    pub async fn access_all(&self) -> ClientResult<AccessReport> {
+        let mut session = self.session.lock().await;
+        self.ensure_login(&mut session).await?;
+        Ok(access_all(&self.client, &mut session).await)
+    }
+
+    /// 确保会话拥有`service`的访问权限，返回可用于调用接口的会话
+    ///
+    /// 返回的会话关闭了自动重新认证，访问权限失效时由[`CquClient::call`]重新获取
+    #[cfg(any(feature = "mycqu", feature = "card"))]
+    async fn session_with(&self, service: Service) -> ClientResult<Session> {
+        let mut session = self.session.lock().await;
+        if !session.has_access(service) {
+            self.ensure_login(&mut session).await?;
+            self.access(&mut session, service).await?;
+        }
+
+        Ok(session.clone().clear_credential_provider())
+    }
+
+    #[cfg(any(feature = "mycqu", feature = "card"))]
+    async fn access(&self, session: &mut Session, service: Service) -> ClientResult<()> {
+        match service {
+            #[cfg(feature = "mycqu")]
+            Service::MyCQU => access_mycqu(&self.client, session).await?,
+            #[cfg(feature = "card")]
+            Service::Card => access_card(&self.client, session).await?,
+        }
+        Ok(())
+    }
+
+    /// 重新登陆并获取`service`的访问权限，`stale`为调用失败时使用的会话
+    ///
+    /// 其他调用已经重新获取了访问权限时直接复用
+    #[cfg(any(feature = "mycqu", feature = "card"))]
+    async fn reaccess(&self, service: Service, stale: &Session) -> ClientResult<Session> {
+        let mut session = self.session.lock().await;
+        let refreshed = match service {
+            #[cfg(feature = "mycqu")]
+            Service::MyCQU => {
+                session.access_infos.read().mycqu_access_info
+                    != stale.access_infos.read().mycqu_access_info
+            }
+            #[cfg(feature = "card")]
+            Service::Card => {
+                session.access_infos.read().card_access_info
+                    != stale.access_infos.read().card_access_info
+            }
+        };
+        if !refreshed {
+            // 统一身份认证的登陆状态可能同样已经失效，重新登陆时仍会复用有效的登陆状态
+            session.is_login = false;
+            self.ensure_login(&mut session).await?;
+            self.access(&mut session, service).await?;
+        }
+
+        Ok(session.clone().clear_credential_provider())
+    }
+
+    /// 使用拥有`service`访问权限的会话调用接口`f`，访问权限失效时重新获取后再调用一次
+    ///
+    /// 重新获取的访问信息及`f`对会话访问信息的修改（如获取的synjones-auth）会写回该客户端的会话
+    #[cfg(any(feature = "mycqu", feature = "card"))]
+    async fn call<T, E>(
+        &self,
+        service: Service,
+        f: impl AsyncFn(&mut Session) -> ApiResult<T, E>,
+    ) -> ClientResult<T>
+    where
+        E: RsMyCQUError,
+        ClientError: From<ApiError<E>>,
+    {
+        let mut session = self.session_with(service).await?;
+        let mut access_infos = session.access_infos();
+        let result = match f(&mut session).await {
+            Err(ApiError::NotAccess) => {
+                session = self.reaccess(service, &session).await?;
+                access_infos = session.access_infos();
+                f(&mut session).await
+            }
+            result => result,
+        };
+
+        let updated = session.access_infos();
+        if updated != access_infos {
+            *self.session.lock().await.access_infos.get_mut() = updated;
+        }
+        Ok(result?)
+    }
+}
+
+#[cfg(feature = "mycqu")]
+impl CquClient {
+    /// 当前登陆用户的信息，第一次获取后被缓存
+    pub async fn user(&self) -> ClientResult<&User> {
+        self.user
+            .get_or_try_init(|| async {
+                self.call(Service::MyCQU, async |session| {
+                    User::fetch_self(&self.client, session).await
+                })
+                .await
+            })
+            .await
+    }
+
+    /// 成绩，`is_minor`为`true`时获取辅修成绩，见[`Score::fetch_self`]
+    pub async fn scores(&self, is_minor: bool) -> ClientResult<Vec<Score>> {
+        self.call(Service::MyCQU, async |session| {
+            Score::fetch_self(&self.client, session, is_minor).await
+        })
+        .await
+    }
+
+    /// 监听成绩变化的[`ScoreWatcher`]，`is_minor`为`true`时监听辅修成绩
+    ///
+    /// 创建时登陆并获取教务网访问权限，监听器使用与该客户端共享Cookie的会话，
+    /// 访问权限失效时通过[自动重新认证](crate::session::reauth)恢复
+    pub async fn score_watcher(&self, is_minor: bool) -> ClientResult<ScoreWatcher> {
+        let session = self
+            .session_with(Service::MyCQU)
+            .await?
+            .set_credential_provider(self.credentials.clone());
+        Ok(ScoreWatcher::new(self.client.clone(), session).set_is_minor(is_minor))
+    }
+
+    /// 成绩`score`的组成明细，见[`Score::fetch_detail`]
+    pub async fn score_detail(&self, score: &Score) -> ClientResult<ScoreDetail> {
+        self.call(Service::MyCQU, async |session| {
+            score.fetch_detail(&self.client, session).await
+        })
+        .await
+    }
+
+    /// 绩点与排名，`is_minor`为`true`时获取辅修专业的绩点与排名，见[`GPARanking::fetch_self`]
+    pub async fn gpa_ranking(&self, is_minor: bool) -> ClientResult<GPARanking> {
+        self.call(Service::MyCQU, async |session| {
+            GPARanking::fetch_self(&self.client, session, is_minor).await
+        })
+        .await
+    }
+
+    /// 主修与辅修专业的绩点与排名，见[`GPARankingReport::fetch_self`]
+    pub async fn gpa_ranking_report(&self) -> ClientResult<GPARankingReport> {
+        self.call(Service::MyCQU, async |session| {
+            GPARankingReport::fetch_self(&self.client, session).await
+        })
+        .await
+    }
+
+    /// 考试安排，见[`Exam::fetch_all`]
+    pub async fn exams(&self) -> ClientResult<Vec<Exam>> {
+        let student_id = &self.user().await?.code;
+        self.call(Service::MyCQU, async |session| {
+            Exam::fetch_all(&self.client, session, student_id).await
+        })
+        .await
+    }
+
+    /// 当前学期的信息，见[`CQUSessionInfo::fetch_curr`]
+    pub async fn current_session_info(&self) -> ClientResult<CQUSessionInfo> {
+        self.call(Service::MyCQU, async |session| {
+            CQUSessionInfo::fetch_curr(&self.client, session).await
+        })
+        .await
+    }
+
+    /// 学期`cqu_session_id`的课表，见[`CourseTimetable::fetch_curr`]
+    pub async fn timetable(&self, cqu_session_id: u16) -> ClientResult<Vec<CourseTimetable>> {
+        let student_id = &self.user().await?.code;
+        self.call(Service::MyCQU, async |session| {
+            CourseTimetable::fetch_curr(&self.client, session, student_id, cqu_session_id).await
+        })
+        .await
+    }
+}
+
+#[cfg(feature = "card")]
+impl CquClient {
+    /// 校园卡信息，见[`Card::fetch_self`]
+    pub async fn card(&self) -> ClientResult<Card> {
+        self.call(Service::Card, async |session| {
+            Card::fetch_self(&self.client, session).await
+        })
+        .await
+    }
+
+    /// 校园卡余额，单位为分
+    pub async fn card_balance(&self) -> ClientResult<u64> {
+        Ok(self.card().await?.amount)
+    }
+
+    /// 校园卡账单，见[`Card::fetch_bill`]
+    ///
+    /// 查询账单所需的校园卡信息在第一次获取后被缓存
+    pub async fn card_bills(
+        &self,
+        start_date: impl AsRef<str>,
+        end_date: impl AsRef<str>,
+        page: u16,
+        row: u16,
+    ) -> ClientResult<Vec<Bill>> {
+        let card = self.card.get_or_try_init(|| self.card()).await?;
+        let (start_date, end_date) = (start_date.as_ref(), end_date.as_ref());
+        self.call(Service::Card, async |session| {
+            card.fetch_bill(&self.client, session, start_date, end_date, page, row)
+                .await
+        })
+        .await
+    }
+
+    /// 宿舍水电费，见[`EnergyFees::fetch_self`]
+    pub async fn energy_fees(
+        &self,
+        room: impl AsRef<str>,
+        is_huxi: bool,
+    ) -> ClientResult<EnergyFees> {
+        self.call(Service::Card, async |session| {
+            EnergyFees::fetch_self(&self.client, session, room.as_ref(), is_huxi).await
+        })
+        .await
+    }
+}
@@ -0,0 +1,50 @@
+//! 包含会在[`CquClient`](crate::CquClient)中出现的所有错误
+
+use snafu::prelude::*;
+
+#[cfg(feature = "card")]
+use crate::errors::card::CardError;
+#[cfg(feature = "mycqu")]
+use crate::errors::mycqu::MyCQUError;
+use crate::{
+    errors::{ApiError, sso::SSOError},
+    sso::LoginResult,
+};
+
+/// ClientError
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub(crate)))]
+pub enum ClientError {
+    /// 自动登陆统一身份认证失败时抛出，如账号密码错误或需要验证码
+    #[snafu(display("Login Failed: {result:?}"))]
+    LoginFailed {
+        /// 登陆结果
+        result: LoginResult,
+    },
+
+    /// 由[`sso`](crate::sso)引发的错误
+    #[snafu(display("SSO Error: {source}"), context(false))]
+    SSO {
+        /// 错误来源
+        source: ApiError<SSOError>,
+    },
+
+    /// 由[`mycqu`](crate::mycqu)引发的错误
+    #[cfg(feature = "mycqu")]
+    #[snafu(display("MyCQU Error: {source}"), context(false))]
+    MyCQU {
+        /// 错误来源
+        source: ApiError<MyCQUError>,
+    },
+
+    /// 由[`card`](crate::card)引发的错误
+    #[cfg(feature = "card")]
+    #[snafu(display("Card Error: {source}"), context(false))]
+    Card {
+        /// 错误来源
+        source: ApiError<CardError>,
+    },
+}
+
+/// ClientResult
+pub type ClientResult<T> = Result<T, ClientError>;
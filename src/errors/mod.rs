@@ -15,6 +15,9 @@ pub mod mycqu;
 #[cfg(feature = "card")]
 pub mod card;
 
+#[cfg(feature = "sso")]
+pub mod client;
+
 mod sealed {
     use crate::errors::sso;
 
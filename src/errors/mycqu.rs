@@ -13,6 +13,9 @@ pub enum MyCQUError {
     #[snafu(display("获取访问权限失败"))]
     /// 获取访问权限失败
     AccessError,
+    #[snafu(display("成绩缺少ID，无法获取成绩明细"))]
+    /// 成绩缺少ID，无法获取成绩明细
+    MissingScoreId,
 }
 
 impl crate::errors::RsMyCQUError for MyCQUError {}
@@ -13,4 +13,13 @@ pub enum SessionError {
         /// 错误信息
         source: ReqwestError,
     },
+
+    /// 回放录像时，录像中没有与请求相匹配的记录
+    #[snafu(display("No recorded interaction matches {method} {url}"))]
+    CassetteMismatch {
+        /// 请求方法
+        method: String,
+        /// 脱敏后的请求链接
+        url: String,
+    },
 }
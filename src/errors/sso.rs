@@ -21,6 +21,15 @@ pub enum SSOError {
     #[snafu(display("Logout Error"))]
     LogoutError,
 
+    /// 当CAS服务票据校验失败时抛出
+    #[snafu(display("Ticket Validation Error {code}: {msg}"))]
+    TicketValidationError {
+        /// 统一身份认证返回的错误码，如`INVALID_TICKET`
+        code: String,
+        /// 统一身份认证返回的错误信息
+        msg: String,
+    },
+
     /// 由教务网引发的错误
     #[snafu(display("{msg}"))]
     UnknownSSOError {
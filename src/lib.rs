@@ -5,6 +5,9 @@
 //! - [重庆大学单点登陆(SSO)](sso)
 //! - [重庆大学教务网相关功能](mycqu)
 //!     - [获取访问教务网API权限](mycqu::access_mycqu)
+//! - 自动登陆并获取服务访问权限的高层客户端[`CquClient`]
+//! - 阻塞式接口`blocking`（需启用`blocking` feature）
+//! - 用于端到端测试的本地模拟服务器`mock`（需启用`mock` feature）
 //!
 
 #![warn(missing_docs)]
@@ -21,11 +24,22 @@ pub mod session;
 #[cfg(feature = "sso")]
 pub mod sso;
 
+#[cfg(feature = "sso")]
+pub mod cqu_client;
+#[cfg(feature = "sso")]
+pub use cqu_client::CquClient;
+
 #[cfg(feature = "mycqu")]
 pub mod mycqu;
 
 #[cfg(feature = "card")]
 pub mod card;
 
+#[cfg(feature = "blocking")]
+pub mod blocking;
+
+#[cfg(feature = "mock")]
+pub mod mock;
+
 // #[cfg(feature = "library")]
 // pub mod library;
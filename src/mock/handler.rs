@@ -0,0 +1,653 @@
+//! 模拟服务器的状态与路由
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use http::{Method, Response, StatusCode, header, request::Parts};
+use hyper::body::Bytes;
+use reqwest::Url;
+
+use crate::{
+    mock::{DEFAULT_AUTH, DEFAULT_PASSWORD, DEFAULT_STUDENT_ID, MockFailure},
+    session::cassette::{Cassette, RecordedResponse},
+    sso::{QrLoginStatus, encrypt::encrypt_password},
+    utils::consts::{
+        CARD_BLADE_AUTH_PATH, CARD_GET_BILL_PATH, CARD_GET_CARD_PATH, CARD_GET_DORM_FEE_PATH,
+        CARD_HALL_TICKET_PATH, CARD_PAGE_PATH, CARD_PAGE_TICKET_POST_FORM_PATH,
+        MYCQU_AUTHORIZE_PATH, MYCQU_SERVICE_PATH, MYCQU_TOKEN_PATH, SSO_LOGIN_PATH,
+        SSO_LOGOUT_PATH, SSO_QR_GENERATE_PATH, SSO_QR_IMAGE_PATH, SSO_QR_STATUS_PATH,
+        SSO_SERVICE_VALIDATE_PATH,
+    },
+};
+
+/// 登陆页面中用于加密密码的密钥
+const LOGIN_CROYPTO: &str = "IGEOE4OMIBo=";
+
+/// 统一身份认证登陆后的跳转地址
+const SSO_HOME_PATH: &str = "/sso/home";
+
+/// 校园卡身份认证服务回调地址，即去除查询参数的`CARD_SERVICE_PATH`
+const CARD_PRELOGIN_PATH: &str = "/ias/prelogin";
+
+/// 校园卡身份认证服务返回`ssoticketid`的页面
+const CARD_SSO_TICKET_PATH: &str = "/ias/ssoticket";
+
+const TGC_COOKIE: &str = "TGC";
+const MYCQU_SESSION_COOKIE: &str = "MYCQU_SESSION";
+const CARD_SESSION_COOKIE: &str = "hallticket";
+const SYNJONES_AUTH_COOKIE: &str = "synjones-auth";
+
+/// 扫码登陆二维码图片的内容，只包含PNG文件头
+const QR_IMAGE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// 提供接口数据的请求录像
+const CASSETTES: [&str; 7] = [
+    include_str!("../mycqu/tests/cassettes/user.json"),
+    include_str!("../mycqu/tests/cassettes/score.json"),
+    include_str!("../mycqu/tests/cassettes/exam.json"),
+    include_str!("../mycqu/tests/cassettes/course.json"),
+    include_str!("../mycqu/tests/cassettes/enroll.json"),
+    include_str!("../card/tests/cassettes/card.json"),
+    include_str!("../card/tests/cassettes/energy_fees.json"),
+];
+
+/// 录像中的一条响应，按请求方法与路径索引
+#[derive(Debug)]
+struct RecordedRoute {
+    method: String,
+    path: String,
+    query: Option<String>,
+    response: RecordedResponse,
+}
+
+/// 解析后的请求
+struct MockRequest<'a> {
+    method: &'a Method,
+    path: &'a str,
+    raw_query: Option<&'a str>,
+    query: HashMap<String, String>,
+    cookies: HashMap<String, String>,
+    form: HashMap<String, String>,
+    bearer: Option<String>,
+}
+
+impl<'a> MockRequest<'a> {
+    fn new(parts: &'a Parts, body: &[u8]) -> Self {
+        let raw_query = parts.uri.query();
+        let cookies = parts
+            .headers
+            .get_all(header::COOKIE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(';'))
+            .filter_map(|pair| pair.split_once('='))
+            .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+            .collect();
+        let bearer = parts
+            .headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .map(ToString::to_string);
+
+        MockRequest {
+            method: &parts.method,
+            path: parts.uri.path(),
+            raw_query,
+            query: parse_pairs(raw_query.unwrap_or_default().as_bytes()),
+            cookies,
+            form: parse_pairs(body),
+            bearer,
+        }
+    }
+
+    fn has_cookie(&self, name: &str, valid: &HashSet<String>) -> bool {
+        self.cookies
+            .get(name)
+            .is_some_and(|value| valid.contains(value))
+    }
+}
+
+fn parse_pairs(input: &[u8]) -> HashMap<String, String> {
+    url::form_urlencoded::parse(input).into_owned().collect()
+}
+
+/// 模拟服务器状态，包括账号信息、已签发的票据与令牌以及待注入的失败场景
+#[derive(Debug)]
+pub(super) struct MockState {
+    root: String,
+    auth: String,
+    password: String,
+    counter: u64,
+    flowkeys: HashSet<String>,
+    tgts: HashSet<String>,
+    /// 二维码ID与其扫码状态
+    qr_codes: HashMap<String, QrLoginStatus>,
+    /// 服务票据与其对应的服务地址
+    service_tickets: HashMap<String, String>,
+    mycqu_sessions: HashSet<String>,
+    oauth_codes: HashSet<String>,
+    mycqu_tokens: HashSet<String>,
+    refresh_tokens: HashSet<String>,
+    sso_ticket_ids: HashSet<String>,
+    card_sessions: HashSet<String>,
+    page_tickets: HashSet<String>,
+    synjones_tokens: HashSet<String>,
+    failures: VecDeque<(String, MockFailure)>,
+    recorded: Vec<RecordedRoute>,
+}
+
+impl MockState {
+    pub(super) fn new(root: String) -> Self {
+        let recorded = CASSETTES
+            .iter()
+            .flat_map(|cassette| {
+                Cassette::from_json(cassette)
+                    .expect("committed cassette should be valid")
+                    .interactions
+            })
+            .map(|interaction| {
+                let url = Url::parse(&interaction.request.url)
+                    .expect("committed cassette should contain valid url");
+                RecordedRoute {
+                    method: interaction.request.method,
+                    path: url.path().to_string(),
+                    query: url.query().map(ToString::to_string),
+                    response: interaction.response,
+                }
+            })
+            .collect();
+
+        MockState {
+            root,
+            auth: DEFAULT_AUTH.to_string(),
+            password: DEFAULT_PASSWORD.to_string(),
+            counter: 0,
+            flowkeys: HashSet::new(),
+            tgts: HashSet::new(),
+            qr_codes: HashMap::new(),
+            service_tickets: HashMap::new(),
+            mycqu_sessions: HashSet::new(),
+            oauth_codes: HashSet::new(),
+            mycqu_tokens: HashSet::new(),
+            refresh_tokens: HashSet::new(),
+            sso_ticket_ids: HashSet::new(),
+            card_sessions: HashSet::new(),
+            page_tickets: HashSet::new(),
+            synjones_tokens: HashSet::new(),
+            failures: VecDeque::new(),
+            recorded,
+        }
+    }
+
+    pub(super) fn set_account(&mut self, auth: String, password: String) {
+        self.auth = auth;
+        self.password = password;
+    }
+
+    pub(super) fn push_failure(&mut self, path: String, failure: MockFailure) {
+        self.failures.push_back((path, failure));
+    }
+
+    pub(super) fn expire_tokens(&mut self) {
+        self.mycqu_tokens.clear();
+        self.card_sessions.clear();
+        self.synjones_tokens.clear();
+    }
+
+    pub(super) fn set_qr_status(&mut self, qr_id: &str, status: QrLoginStatus) -> bool {
+        self.qr_codes
+            .get_mut(qr_id)
+            .map(|current| *current = status)
+            .is_some()
+    }
+
+    pub(super) fn revoke_refresh_tokens(&mut self) {
+        self.refresh_tokens.clear();
+    }
+
+    pub(super) fn respond(&mut self, parts: &Parts, body: &[u8]) -> Response<Bytes> {
+        let request = MockRequest::new(parts, body);
+
+        if let Some(index) = self
+            .failures
+            .iter()
+            .position(|(path, _)| path == request.path)
+        {
+            let (_, failure) = self.failures.remove(index).unwrap();
+            match failure {
+                MockFailure::Unauthorized => return empty(StatusCode::UNAUTHORIZED),
+                MockFailure::MalformedJson => {
+                    return response(
+                        StatusCode::OK,
+                        "application/json",
+                        r#"{"status":"success","data":"#,
+                    );
+                }
+                MockFailure::Status(status) => {
+                    return empty(
+                        StatusCode::from_u16(status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+                    );
+                }
+                MockFailure::ExpiredToken => self.expire_tokens(),
+            }
+        }
+
+        self.route(&request)
+    }
+
+    fn route(&mut self, request: &MockRequest) -> Response<Bytes> {
+        match (request.method, request.path) {
+            (&Method::GET, SSO_LOGIN_PATH) => self.login_page(request),
+            (&Method::POST, SSO_LOGIN_PATH) => self.submit_login(request),
+            (&Method::GET, SSO_LOGOUT_PATH) => self.logout(request),
+            (&Method::GET, SSO_SERVICE_VALIDATE_PATH) => self.service_validate(request),
+            (&Method::GET, SSO_HOME_PATH) => response(StatusCode::OK, "text/html", "ok"),
+            (&Method::GET, SSO_QR_GENERATE_PATH) => self.qr_generate(),
+            (&Method::GET, SSO_QR_IMAGE_PATH) => self.qr_image(request),
+            (&Method::GET, SSO_QR_STATUS_PATH) => self.qr_status(request),
+            (&Method::GET, MYCQU_SERVICE_PATH) => self.mycqu_cas(request),
+            (&Method::GET, MYCQU_AUTHORIZE_PATH) => self.mycqu_authorize(request),
+            (&Method::POST, MYCQU_TOKEN_PATH) => self.mycqu_token(request),
+            (&Method::GET, CARD_PRELOGIN_PATH) => self.card_prelogin(request),
+            (&Method::GET, CARD_SSO_TICKET_PATH) => self.card_sso_ticket(request),
+            (&Method::POST, CARD_HALL_TICKET_PATH) => self.card_hall_ticket(request),
+            (&Method::POST, CARD_PAGE_PATH) => self.card_page(request),
+            (&Method::POST, CARD_BLADE_AUTH_PATH) => self.synjones_token(request),
+            (_, CARD_GET_DORM_FEE_PATH) => {
+                let authorized = request
+                    .cookies
+                    .get(SYNJONES_AUTH_COOKIE)
+                    .and_then(|value| value.strip_prefix("bearer "))
+                    .is_some_and(|token| self.synjones_tokens.contains(token));
+                self.recorded_if(request, authorized)
+            }
+            (_, CARD_GET_CARD_PATH | CARD_GET_BILL_PATH) => {
+                let authorized = request.has_cookie(CARD_SESSION_COOKIE, &self.card_sessions);
+                self.recorded_if(request, authorized)
+            }
+            _ => {
+                let authorized = request
+                    .bearer
+                    .as_ref()
+                    .is_some_and(|token| self.mycqu_tokens.contains(token));
+                self.recorded_if(request, authorized)
+            }
+        }
+    }
+
+    fn next_id(&mut self, prefix: &str) -> String {
+        self.counter += 1;
+        format!("{prefix}-{}", self.counter)
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.root, path)
+    }
+
+    /// 签发服务票据，返回携带票据的服务地址
+    fn issue_service_ticket(&mut self, service: &str) -> Option<String> {
+        let mut url = Url::parse(service).ok()?;
+        let ticket = self.next_id("ST");
+        url.query_pairs_mut().append_pair("ticket", &ticket);
+        self.service_tickets.insert(ticket, service.to_string());
+
+        Some(url.into())
+    }
+
+    /// 校验并消耗签发给路径为`path`的服务的票据
+    fn take_service_ticket(&mut self, request: &MockRequest, path: &str) -> bool {
+        request
+            .query
+            .get("ticket")
+            .and_then(|ticket| self.service_tickets.remove(ticket))
+            .and_then(|service| Url::parse(&service).ok())
+            .is_some_and(|service| service.path() == path)
+    }
+
+    fn login_page(&mut self, request: &MockRequest) -> Response<Bytes> {
+        if !request.has_cookie(TGC_COOKIE, &self.tgts) {
+            return self.login_page_html(StatusCode::OK, None);
+        }
+
+        let location = match request.query.get("service") {
+            Some(service) => self.issue_service_ticket(service),
+            None => {
+                let mut url = Url::parse(&self.url(SSO_LOGIN_PATH)).ok();
+                if let Some(url) = url.as_mut() {
+                    url.query_pairs_mut()
+                        .append_pair("service", &self.url(SSO_HOME_PATH));
+                }
+                url.map(Into::into)
+            }
+        };
+
+        match location {
+            Some(location) => redirect(&location),
+            None => empty(StatusCode::BAD_REQUEST),
+        }
+    }
+
+    fn login_page_html(&mut self, status: StatusCode, error: Option<&str>) -> Response<Bytes> {
+        let flowkey = self.next_id("flowkey");
+        self.flowkeys.insert(flowkey.clone());
+        let error = error
+            .map(|msg| format!(r#"<span class="login_auth_error" id="msg">{msg}</span>"#))
+            .unwrap_or_default();
+
+        response(
+            status,
+            "text/html;charset=utf-8",
+            format!(
+                r#"<!DOCTYPE html><html><body><p id="login-croypto">{LOGIN_CROYPTO}</p><p id="login-page-flowkey">{flowkey}</p>{error}</body></html>"#
+            ),
+        )
+    }
+
+    fn submit_login(&mut self, request: &MockRequest) -> Response<Bytes> {
+        let form = &request.form;
+        let valid_flow = form
+            .get("execution")
+            .is_some_and(|flowkey| self.flowkeys.remove(flowkey));
+        let valid_account = match form.get("type").map(String::as_str) {
+            // 扫码登陆要求二维码已在手机上确认，二维码在登陆后失效
+            Some("qrcode") => form
+                .get("qrcodeId")
+                .and_then(|qr_id| self.qr_codes.remove(qr_id))
+                .is_some_and(|status| status == QrLoginStatus::Confirmed),
+            _ => {
+                form.get("username") == Some(&self.auth)
+                    && form.get("password").is_some_and(|password| {
+                        encrypt_password(LOGIN_CROYPTO, &self.password)
+                            .is_ok_and(|valid| &valid == password)
+                    })
+            }
+        };
+
+        if !(valid_flow && valid_account) {
+            return self.login_page_html(StatusCode::UNAUTHORIZED, Some("用户名或密码错误"));
+        }
+
+        let tgt = self.next_id("TGT");
+        self.tgts.insert(tgt.clone());
+        with_cookie(
+            redirect(&self.url(SSO_HOME_PATH)),
+            format!("{TGC_COOKIE}={tgt}; Path=/; HttpOnly"),
+        )
+    }
+
+    fn qr_generate(&mut self) -> Response<Bytes> {
+        let qr_id = self.next_id("qrcode");
+        self.qr_codes.insert(qr_id.clone(), QrLoginStatus::Waiting);
+        response(
+            StatusCode::OK,
+            "application/json",
+            format!(
+                r#"{{"qrcodeId":"{qr_id}","content":"{}?qrcodeId={qr_id}"}}"#,
+                self.url(SSO_LOGIN_PATH)
+            ),
+        )
+    }
+
+    fn qr_image(&self, request: &MockRequest) -> Response<Bytes> {
+        match request.query.get("qrcodeId") {
+            Some(qr_id) if self.qr_codes.contains_key(qr_id) => {
+                response(StatusCode::OK, "image/png", QR_IMAGE)
+            }
+            _ => empty(StatusCode::NOT_FOUND),
+        }
+    }
+
+    /// 返回二维码的扫码状态，未知的二维码视为已过期
+    fn qr_status(&self, request: &MockRequest) -> Response<Bytes> {
+        let status = match request
+            .query
+            .get("qrcodeId")
+            .and_then(|qr_id| self.qr_codes.get(qr_id))
+        {
+            Some(QrLoginStatus::Waiting) => 0,
+            Some(QrLoginStatus::Scanned) => 1,
+            Some(QrLoginStatus::Confirmed) => 2,
+            Some(QrLoginStatus::Expired) | None => 3,
+        };
+        response(
+            StatusCode::OK,
+            "application/json",
+            format!(r#"{{"status":{status}}}"#),
+        )
+    }
+
+    fn logout(&mut self, request: &MockRequest) -> Response<Bytes> {
+        if let Some(tgt) = request.cookies.get(TGC_COOKIE) {
+            self.tgts.remove(tgt);
+        }
+
+        with_cookie(
+            response(StatusCode::OK, "text/html", "ok"),
+            format!("{TGC_COOKIE}=; Path=/; Max-Age=0"),
+        )
+    }
+
+    fn service_validate(&mut self, request: &MockRequest) -> Response<Bytes> {
+        let ticket = request.query.get("ticket").cloned().unwrap_or_default();
+        let valid = self.service_tickets.remove(&ticket).is_some_and(|service| {
+            request
+                .query
+                .get("service")
+                .is_some_and(|expected| *expected == service)
+        });
+
+        let body = if valid {
+            format!(
+                r#"<cas:serviceResponse xmlns:cas="http://www.yale.edu/tp/cas"><cas:authenticationSuccess><cas:user>{}</cas:user><cas:attributes><cas:studentId>{DEFAULT_STUDENT_ID}</cas:studentId></cas:attributes></cas:authenticationSuccess></cas:serviceResponse>"#,
+                self.auth
+            )
+        } else {
+            format!(
+                r#"<cas:serviceResponse xmlns:cas="http://www.yale.edu/tp/cas"><cas:authenticationFailure code="INVALID_TICKET">Ticket {ticket} not recognized</cas:authenticationFailure></cas:serviceResponse>"#
+            )
+        };
+
+        response(StatusCode::OK, "application/xml;charset=utf-8", body)
+    }
+
+    fn mycqu_cas(&mut self, request: &MockRequest) -> Response<Bytes> {
+        if !self.take_service_ticket(request, MYCQU_SERVICE_PATH) {
+            return empty(StatusCode::UNAUTHORIZED);
+        }
+
+        let session = self.next_id("mycqu");
+        self.mycqu_sessions.insert(session.clone());
+        with_cookie(
+            response(StatusCode::OK, "text/html", "ok"),
+            format!("{MYCQU_SESSION_COOKIE}={session}; Path=/"),
+        )
+    }
+
+    fn mycqu_authorize(&mut self, request: &MockRequest) -> Response<Bytes> {
+        let Some(redirect_uri) = request.query.get("redirect_uri") else {
+            return empty(StatusCode::BAD_REQUEST);
+        };
+        if !request.has_cookie(MYCQU_SESSION_COOKIE, &self.mycqu_sessions) {
+            return empty(StatusCode::UNAUTHORIZED);
+        }
+
+        let code = self.next_id("code");
+        self.oauth_codes.insert(code.clone());
+        redirect(&format!("{redirect_uri}?code={code}&state="))
+    }
+
+    fn mycqu_token(&mut self, request: &MockRequest) -> Response<Bytes> {
+        let granted = match request.form.get("grant_type").map(String::as_str) {
+            Some("authorization_code") => request
+                .form
+                .get("code")
+                .is_some_and(|code| self.oauth_codes.remove(code)),
+            Some("refresh_token") => request
+                .form
+                .get("refresh_token")
+                .is_some_and(|token| self.refresh_tokens.remove(token)),
+            _ => false,
+        };
+        if !granted {
+            return response(
+                StatusCode::BAD_REQUEST,
+                "application/json",
+                r#"{"error":"invalid_grant"}"#,
+            );
+        }
+
+        let token = self.next_id("token");
+        self.mycqu_tokens.insert(token.clone());
+        let refresh_token = self.next_id("refresh");
+        self.refresh_tokens.insert(refresh_token.clone());
+        response(
+            StatusCode::OK,
+            "application/json",
+            format!(
+                r#"{{"access_token":"{token}","token_type":"bearer","expires_in":3600,"refresh_token":"{refresh_token}"}}"#
+            ),
+        )
+    }
+
+    fn card_prelogin(&mut self, request: &MockRequest) -> Response<Bytes> {
+        if !self.take_service_ticket(request, CARD_PRELOGIN_PATH) {
+            return empty(StatusCode::UNAUTHORIZED);
+        }
+
+        let sso_ticket_id = self.next_id("ssoticket");
+        self.sso_ticket_ids.insert(sso_ticket_id.clone());
+        redirect(&format!(
+            "{}?ssoticketid={sso_ticket_id}",
+            self.url(CARD_SSO_TICKET_PATH)
+        ))
+    }
+
+    fn card_sso_ticket(&self, request: &MockRequest) -> Response<Bytes> {
+        match request
+            .query
+            .get("ssoticketid")
+            .filter(|id| self.sso_ticket_ids.contains(*id))
+        {
+            Some(id) => response(
+                StatusCode::OK,
+                "text/html;charset=utf-8",
+                format!(
+                    r#"<html><body><input type="hidden" id="ssoticketid" value="{id}"></body></html>"#
+                ),
+            ),
+            None => empty(StatusCode::UNAUTHORIZED),
+        }
+    }
+
+    fn card_hall_ticket(&mut self, request: &MockRequest) -> Response<Bytes> {
+        if !request
+            .form
+            .get("ssoticketid")
+            .is_some_and(|id| self.sso_ticket_ids.remove(id))
+        {
+            return empty(StatusCode::UNAUTHORIZED);
+        }
+
+        let session = self.next_id("hall");
+        self.card_sessions.insert(session.clone());
+        with_cookie(
+            response(StatusCode::OK, "text/html", "ok"),
+            format!("{CARD_SESSION_COOKIE}={session}; Path=/"),
+        )
+    }
+
+    fn card_page(&mut self, request: &MockRequest) -> Response<Bytes> {
+        if !request.has_cookie(CARD_SESSION_COOKIE, &self.card_sessions) {
+            return empty(StatusCode::UNAUTHORIZED);
+        }
+
+        let ticket = self.next_id("page");
+        self.page_tickets.insert(ticket.clone());
+        response(
+            StatusCode::OK,
+            "text/html;charset=utf-8",
+            format!(
+                "<script>window.location.href = '{}?ticket={ticket}';</script>",
+                self.url(CARD_PAGE_TICKET_POST_FORM_PATH)
+            ),
+        )
+    }
+
+    fn synjones_token(&mut self, request: &MockRequest) -> Response<Bytes> {
+        if !request.has_cookie(CARD_SESSION_COOKIE, &self.card_sessions)
+            || !request
+                .form
+                .get("ticket")
+                .is_some_and(|ticket| self.page_tickets.remove(ticket))
+        {
+            return empty(StatusCode::UNAUTHORIZED);
+        }
+
+        let token = self.next_id("synjones");
+        self.synjones_tokens.insert(token.clone());
+        response(
+            StatusCode::OK,
+            "application/json",
+            format!(r#"{{"code":200,"data":{{"access_token":"{token}"}}}}"#),
+        )
+    }
+
+    /// 凭据有效时返回录像中的响应，优先使用查询参数一致的记录
+    fn recorded_if(&self, request: &MockRequest, authorized: bool) -> Response<Bytes> {
+        let mut candidates = self
+            .recorded
+            .iter()
+            .filter(|route| route.method == request.method.as_str() && route.path == request.path)
+            .peekable();
+        if candidates.peek().is_none() {
+            return empty(StatusCode::NOT_FOUND);
+        }
+        if !authorized {
+            return empty(StatusCode::UNAUTHORIZED);
+        }
+
+        let candidates: Vec<_> = candidates.collect();
+        let route = candidates
+            .iter()
+            .find(|route| route.query.as_deref() == request.raw_query)
+            .unwrap_or(&candidates[0]);
+
+        let mut builder = Response::builder().status(route.response.status);
+        for (name, value) in &route.response.headers {
+            builder = builder.header(name, value);
+        }
+        builder
+            .body(Bytes::from(route.response.body.clone()))
+            .unwrap_or_else(|_| empty(StatusCode::INTERNAL_SERVER_ERROR))
+    }
+}
+
+fn response(status: StatusCode, content_type: &str, body: impl Into<Bytes>) -> Response<Bytes> {
+    Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, content_type)
+        .body(body.into())
+        .expect("mock response should be valid")
+}
+
+fn empty(status: StatusCode) -> Response<Bytes> {
+    Response::builder()
+        .status(status)
+        .body(Bytes::new())
+        .expect("mock response should be valid")
+}
+
+fn redirect(location: &str) -> Response<Bytes> {
+    Response::builder()
+        .status(StatusCode::FOUND)
+        .header(header::LOCATION, location)
+        .body(Bytes::new())
+        .expect("mock response should be valid")
+}
+
+fn with_cookie(mut response: Response<Bytes>, cookie: String) -> Response<Bytes> {
+    if let Ok(cookie) = cookie.parse() {
+        response.headers_mut().append(header::SET_COOKIE, cookie);
+    }
+    response
+}
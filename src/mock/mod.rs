@@ -0,0 +1,182 @@
+//! 用于端到端测试的本地模拟服务器
+//!
+//! [`MockServer`]在本地端口上模拟统一身份认证、教务网与校园卡网站：
+//!
+//! - 统一身份认证：登陆页面（含`p#login-croypto`、`p#login-page-flowkey`）、登陆表单校验、扫码登陆、服务票据签发与`serviceValidate`校验
+//! - 教务网：CAS回调、OAuth授权码与令牌交换、`refresh_token`刷新，以及需要`Authorization`头的JSON接口
+//! - 校园卡：`ssoticketid`跳转、大厅票据、电费页面票据与Synjones令牌，以及账户、账单、水电费接口
+//!
+//! 各接口的数据来自随仓库提交的请求录像，并可通过[`MockServer::fail_next`]脚本化地注入失败场景，
+//! 扫码登陆的二维码状态可通过[`MockServer::set_qr_status`]模拟用户在手机上的操作，
+//! 从而在无网络的环境中测试[`login`](crate::sso::login)、[`start_qr_login`](crate::sso::start_qr_login)、[`access_mycqu`](crate::mycqu::access_mycqu)、
+//! [`access_card`](crate::card::access_card)与全部查询接口
+//!
+//! # Examples
+//! ```rust, no_run
+//! # use rsmycqu::mock::{MockServer, DEFAULT_AUTH, DEFAULT_PASSWORD};
+//! # use rsmycqu::mycqu::{access_mycqu, User};
+//! # use rsmycqu::session::Session;
+//! # use rsmycqu::sso::login;
+//! # async fn mock() {
+//! let server = MockServer::start().await.unwrap();
+//! let client = server.client();
+//! let mut session = Session::new();
+//! login(&client, &mut session, DEFAULT_AUTH, DEFAULT_PASSWORD, false).await.unwrap();
+//! access_mycqu(&client, &mut session).await.unwrap();
+//! let user = User::fetch_self(&client, &session).await.unwrap();
+//! # }
+//! ```
+
+use std::{
+    convert::Infallible,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+
+use http_body_util::{BodyExt, Full};
+use hyper::{Request, Response, body::Bytes, server::conn::http1, service::service_fn};
+use hyper_util::rt::TokioIo;
+use tokio::{net::TcpListener, task::JoinHandle};
+
+use crate::{
+    mock::handler::MockState,
+    session::{Client, Endpoints},
+    sso::QrLoginStatus,
+};
+
+mod handler;
+
+#[cfg(test)]
+mod tests;
+
+/// 模拟服务器默认接受的统一身份认证号
+pub const DEFAULT_AUTH: &str = "mock_auth";
+
+/// 模拟服务器默认接受的密码
+pub const DEFAULT_PASSWORD: &str = "mock_password";
+
+/// 模拟服务器中用户的学号，与录像中的学号一致
+pub const DEFAULT_STUDENT_ID: &str = "20200001";
+
+/// 可注入的失败场景
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum MockFailure {
+    /// 直接返回`401`
+    Unauthorized,
+    /// 使已签发的教务网令牌与校园卡会话全部失效，随后按失效的凭据处理该请求
+    ExpiredToken,
+    /// 返回无法解析的JSON
+    MalformedJson,
+    /// 返回指定状态码与空响应体
+    Status(u16),
+}
+
+/// 本地模拟服务器，被丢弃时停止服务
+#[derive(Debug)]
+pub struct MockServer {
+    root: String,
+    state: Arc<Mutex<MockState>>,
+    task: JoinHandle<()>,
+}
+
+impl MockServer {
+    /// 在`127.0.0.1`的随机端口上启动模拟服务器
+    ///
+    /// 需要在`tokio`运行时中调用，服务器在该运行时中处理请求
+    pub async fn start() -> std::io::Result<Self> {
+        let listener = TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0))).await?;
+        let root = format!("http://{}", listener.local_addr()?);
+        let state = Arc::new(Mutex::new(MockState::new(root.clone())));
+        let task = tokio::spawn(serve(listener, state.clone()));
+
+        Ok(MockServer { root, state, task })
+    }
+
+    /// 模拟服务器的根地址
+    pub fn root(&self) -> &str {
+        &self.root
+    }
+
+    /// 全部服务均指向模拟服务器的[`Endpoints`]
+    pub fn endpoints(&self) -> Endpoints {
+        Endpoints::with_root(&self.root)
+    }
+
+    /// 访问模拟服务器的[`Client`]
+    pub fn client(&self) -> Client {
+        Client::default().set_endpoints(self.endpoints())
+    }
+
+    /// 设置模拟服务器接受的账号与密码
+    pub fn set_account(self, auth: impl Into<String>, password: impl Into<String>) -> Self {
+        self.state
+            .lock()
+            .unwrap()
+            .set_account(auth.into(), password.into());
+        self
+    }
+
+    /// 使下一次路径为`path`的请求以`failure`失败，多次调用时按调用顺序依次生效
+    ///
+    /// `path`为不含查询参数的请求路径，如`/api/sam/score/student/score`
+    pub fn fail_next(&self, path: impl Into<String>, failure: MockFailure) {
+        self.state
+            .lock()
+            .unwrap()
+            .push_failure(path.into(), failure);
+    }
+
+    /// 使已签发的教务网令牌与校园卡会话全部失效，教务网的`refresh_token`仍然有效
+    pub fn expire_tokens(&self) {
+        self.state.lock().unwrap().expire_tokens();
+    }
+
+    /// 设置ID为`qr_id`的二维码的扫码状态，模拟用户扫码、确认或二维码过期，二维码不存在时返回`false`
+    ///
+    /// 二维码由[`start_qr_login`](crate::sso::start_qr_login)获取，初始状态为[`QrLoginStatus::Waiting`]，
+    /// 状态为[`QrLoginStatus::Confirmed`]时[`poll_qr_login`](crate::sso::poll_qr_login)才能完成登陆
+    pub fn set_qr_status(&self, qr_id: &str, status: QrLoginStatus) -> bool {
+        self.state.lock().unwrap().set_qr_status(qr_id, status)
+    }
+
+    /// 使已签发的教务网`refresh_token`全部失效
+    pub fn revoke_refresh_tokens(&self) {
+        self.state.lock().unwrap().revoke_refresh_tokens();
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn serve(listener: TcpListener, state: Arc<Mutex<MockState>>) {
+    loop {
+        let Ok((stream, _)) = listener.accept().await else {
+            continue;
+        };
+        let state = state.clone();
+        tokio::spawn(async move {
+            let service = service_fn(move |request| handle(state.clone(), request));
+            // 连接错误只影响当前连接，无需处理
+            let _ = http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await;
+        });
+    }
+}
+
+async fn handle(
+    state: Arc<Mutex<MockState>>,
+    request: Request<hyper::body::Incoming>,
+) -> Result<Response<Full<Bytes>>, Infallible> {
+    let (parts, body) = request.into_parts();
+    let body = body
+        .collect()
+        .await
+        .map(|body| body.to_bytes())
+        .unwrap_or_default();
+
+    Ok(state.lock().unwrap().respond(&parts, &body).map(Full::new))
+}
@@ -0,0 +1,487 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use rstest::*;
+
+use crate::{
+    CquClient,
+    card::{Card, EnergyFees, access_card},
+    errors::{ApiError, client::ClientError},
+    mock::{DEFAULT_AUTH, DEFAULT_PASSWORD, DEFAULT_STUDENT_ID, MockFailure, MockServer},
+    mycqu::{
+        User, access_mycqu,
+        course::{CQUSession, CQUSessionInfo, CourseTimetable},
+        enroll::{EnrollCourseInfo, EnrollCourseItem},
+        exam::Exam,
+        refresh_mycqu,
+        score::{GPARanking, Score},
+    },
+    session::{
+        Client, Session,
+        access_info::Service,
+        reauth::{Credentials, ReAuthEvent},
+    },
+    sso::{
+        LoginResult, QrLoginStart, QrLoginStatus, access_all, access_service, cas::validate_ticket,
+        login, logout, poll_qr_login, start_qr_login,
+    },
+};
+
+async fn login_session(client: &Client) -> Session {
+    let mut session = Session::new();
+    let res = login(client, &mut session, DEFAULT_AUTH, DEFAULT_PASSWORD, false)
+        .await
+        .unwrap();
+    assert_eq!(res, LoginResult::Success);
+    session
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_mock_login() {
+    let server = MockServer::start().await.unwrap();
+    let client = server.client();
+
+    let mut session = Session::new();
+    let res = login(&client, &mut session, DEFAULT_AUTH, "wrong_password", false)
+        .await
+        .unwrap();
+    assert_eq!(res, LoginResult::IncorrectLoginCredentials);
+    assert!(!session.is_login());
+
+    let mut session = login_session(&client).await;
+    assert!(session.is_login());
+
+    // 已登陆时再次登陆直接复用统一身份认证的登陆状态
+    let res = login(&client, &mut session, DEFAULT_AUTH, DEFAULT_PASSWORD, false)
+        .await
+        .unwrap();
+    assert_eq!(res, LoginResult::Success);
+
+    logout(&client, &mut session).await.unwrap();
+    let res = access_service(&client, &session, server.root()).await;
+    assert!(matches!(res.unwrap_err(), ApiError::NotLogin));
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_mock_qr_login() {
+    let server = MockServer::start().await.unwrap();
+    let client = server.client();
+    let mut session = Session::new();
+
+    let QrLoginStart::Pending(qr_login) =
+        start_qr_login(&client, &mut session, false).await.unwrap()
+    else {
+        panic!("qr login should wait for scanning");
+    };
+    assert!(qr_login.content().contains(qr_login.qr_id()));
+    assert!(!qr_login.image().is_empty());
+
+    let mut poll = async |status| {
+        assert!(server.set_qr_status(qr_login.qr_id(), status));
+        poll_qr_login(&client, &mut session, &qr_login)
+            .await
+            .unwrap()
+    };
+    assert_eq!(poll(QrLoginStatus::Waiting).await, QrLoginStatus::Waiting);
+    assert_eq!(poll(QrLoginStatus::Scanned).await, QrLoginStatus::Scanned);
+    assert_eq!(
+        poll(QrLoginStatus::Confirmed).await,
+        QrLoginStatus::Confirmed
+    );
+
+    assert!(session.is_login());
+    access_mycqu(&client, &mut session).await.unwrap();
+    User::fetch_self(&client, &session).await.unwrap();
+
+    // 已登陆时无需扫码，过期的二维码无法完成登陆
+    assert_eq!(
+        start_qr_login(&client, &mut session, false).await.unwrap(),
+        QrLoginStart::Finished(LoginResult::Success)
+    );
+    let mut session = Session::new();
+    let QrLoginStart::Pending(qr_login) =
+        start_qr_login(&client, &mut session, false).await.unwrap()
+    else {
+        panic!("qr login should wait for scanning");
+    };
+    server.set_qr_status(qr_login.qr_id(), QrLoginStatus::Expired);
+    assert_eq!(
+        poll_qr_login(&client, &mut session, &qr_login)
+            .await
+            .unwrap(),
+        QrLoginStatus::Expired
+    );
+    assert!(!session.is_login());
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_mock_custom_account_and_ticket_validation() {
+    let server = MockServer::start()
+        .await
+        .unwrap()
+        .set_account("custom_auth", "custom_password");
+    let client = server.client();
+
+    let mut session = Session::new();
+    let res = login(
+        &client,
+        &mut session,
+        "custom_auth",
+        "custom_password",
+        false,
+    )
+    .await
+    .unwrap();
+    assert_eq!(res, LoginResult::Success);
+
+    let service = format!("{}/my-service", server.root());
+    let access = access_service(&client, &session, &service).await.unwrap();
+    let user = validate_ticket(&client, &service, &access.ticket)
+        .await
+        .unwrap();
+    assert_eq!(user.user, "custom_auth");
+    assert_eq!(user.attribute("studentId"), Some(DEFAULT_STUDENT_ID));
+
+    // 服务票据只能校验一次
+    assert!(
+        validate_ticket(&client, &service, &access.ticket)
+            .await
+            .is_err()
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_mock_service_maintenance() {
+    let server = MockServer::start().await.unwrap();
+    let client = server.client();
+    server.fail_next("/login", MockFailure::Status(503));
+
+    let res = login(
+        &client,
+        &mut Session::new(),
+        DEFAULT_AUTH,
+        DEFAULT_PASSWORD,
+        false,
+    )
+    .await
+    .unwrap();
+    assert_eq!(res, LoginResult::ServiceMaintenance);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_mock_mycqu() {
+    let server = MockServer::start().await.unwrap();
+    let client = server.client();
+    let mut session = login_session(&client).await;
+    access_mycqu(&client, &mut session).await.unwrap();
+
+    User::fetch_self(&client, &session).await.unwrap();
+    assert!(
+        !Score::fetch_self(&client, &session, false)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    GPARanking::fetch_self(&client, &session, false)
+        .await
+        .unwrap();
+    assert!(
+        !Exam::fetch_all(&client, &session, DEFAULT_STUDENT_ID)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert!(
+        !CQUSession::fetch_all(&client, &session)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    CQUSessionInfo::fetch_all(&client, &session).await.unwrap();
+    let curr = CQUSessionInfo::fetch_curr(&client, &session).await.unwrap();
+    CQUSessionInfo::fetch_detail(&client, &session, curr.session.id.unwrap() as u32)
+        .await
+        .unwrap();
+    CourseTimetable::fetch_curr(
+        &client,
+        &session,
+        DEFAULT_STUDENT_ID,
+        curr.session.id.unwrap(),
+    )
+    .await
+    .unwrap();
+    CourseTimetable::fetch_enroll(&client, &session, DEFAULT_STUDENT_ID)
+        .await
+        .unwrap();
+    let infos = EnrollCourseInfo::fetch_all(&client, &session, true)
+        .await
+        .unwrap();
+    let course_id = &infos.values().next().unwrap()[0].id;
+    EnrollCourseItem::fetch_all(&client, &session, course_id, true)
+        .await
+        .unwrap();
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_mock_card() {
+    let server = MockServer::start().await.unwrap();
+    let client = server.client();
+    let mut session = login_session(&client).await;
+    access_card(&client, &mut session).await.unwrap();
+
+    let card = Card::fetch_self(&client, &session).await.unwrap();
+    card.fetch_bill(&client, &session, "2023-11-10", "2023-12-12", 1, 100)
+        .await
+        .unwrap();
+    EnergyFees::fetch_self(&client, &mut session, "b5321", true)
+        .await
+        .unwrap();
+    // 第二次查询复用会话中保存的Synjones令牌
+    EnergyFees::fetch_self(&client, &mut session, "b5321", true)
+        .await
+        .unwrap();
+    let infos = session.access_infos();
+    assert!(infos.card().unwrap().synjones_auth().is_some());
+    assert_eq!(infos.services(), vec![Service::Card]);
+}
+
+#[rstest]
+#[case::unauthorized(MockFailure::Unauthorized)]
+#[case::expired_token(MockFailure::ExpiredToken)]
+#[tokio::test]
+async fn test_mock_access_lost(#[case] failure: MockFailure) {
+    let server = MockServer::start().await.unwrap();
+    let client = server.client();
+    let mut session = login_session(&client).await;
+    access_mycqu(&client, &mut session).await.unwrap();
+
+    server.fail_next("/authserver/simple-user", failure.clone());
+    let res = User::fetch_self(&client, &session).await;
+    assert!(matches!(res.unwrap_err(), ApiError::NotAccess));
+
+    // 启用自动重新认证后，失败的请求会在重新获取权限后重放
+    let session = session.set_credential_provider(Credentials::new(DEFAULT_AUTH, DEFAULT_PASSWORD));
+    server.fail_next("/authserver/simple-user", failure);
+    User::fetch_self(&client, &session).await.unwrap();
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_mock_reauth_persists_access() {
+    let server = MockServer::start().await.unwrap();
+    let client = server.client();
+    let relogins = Arc::new(AtomicUsize::new(0));
+    let counter = relogins.clone();
+    let mut session = login_session(&client)
+        .await
+        .set_credential_provider(Credentials::new(DEFAULT_AUTH, DEFAULT_PASSWORD))
+        .set_reauth_hook(move |event| {
+            if matches!(event, ReAuthEvent::Started { .. }) {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+    access_mycqu(&client, &mut session).await.unwrap();
+    access_card(&client, &mut session).await.unwrap();
+    let access_infos = session.access_infos();
+
+    // 令牌过期后只重新认证一次，新的访问信息写回以`&Session`调用的会话
+    server.expire_tokens();
+    User::fetch_self(&client, &session).await.unwrap();
+    Score::fetch_self(&client, &session, false).await.unwrap();
+    assert_eq!(relogins.load(Ordering::SeqCst), 1);
+    assert_ne!(session.access_infos().mycqu(), access_infos.mycqu());
+
+    server.expire_tokens();
+    Card::fetch_self(&client, &session).await.unwrap();
+    Card::fetch_self(&client, &session).await.unwrap();
+    assert_eq!(relogins.load(Ordering::SeqCst), 2);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_mock_card_access_lost() {
+    let server = MockServer::start().await.unwrap();
+    let client = server.client();
+    let mut session = login_session(&client)
+        .await
+        .set_credential_provider(Credentials::new(DEFAULT_AUTH, DEFAULT_PASSWORD));
+    access_card(&client, &mut session).await.unwrap();
+    EnergyFees::fetch_self(&client, &mut session, "b5321", true)
+        .await
+        .unwrap();
+    let synjones_auth = session
+        .access_infos()
+        .card()
+        .unwrap()
+        .synjones_auth()
+        .map(ToString::to_string);
+
+    // 重新认证后使用新获取的Synjones令牌重放请求，并写回会话
+    server.expire_tokens();
+    Card::fetch_self(&client, &session).await.unwrap();
+    EnergyFees::fetch_self(&client, &mut session, "b5321", true)
+        .await
+        .unwrap();
+    let refreshed = session
+        .access_infos()
+        .card()
+        .unwrap()
+        .synjones_auth()
+        .map(ToString::to_string);
+    assert!(refreshed.is_some());
+    assert_ne!(refreshed, synjones_auth);
+
+    // 未启用自动重新认证时不会重放
+    server.expire_tokens();
+    let mut session = session.clear_credential_provider();
+    let res = EnergyFees::fetch_self(&client, &mut session, "b5321", true).await;
+    assert!(matches!(res.unwrap_err(), ApiError::NotAccess));
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_mock_malformed_json() {
+    let server = MockServer::start().await.unwrap();
+    let client = server.client();
+    let mut session = login_session(&client).await;
+    access_mycqu(&client, &mut session).await.unwrap();
+
+    server.fail_next("/api/sam/score/student/score", MockFailure::MalformedJson);
+    let res = Score::fetch_self(&client, &session, false).await;
+    assert!(matches!(res.unwrap_err(), ApiError::ModelParse { .. }));
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_mock_refresh_mycqu() {
+    let server = MockServer::start().await.unwrap();
+    let client = server.client();
+    assert_eq!(Session::new().mycqu_access_remaining(), None);
+
+    let mut session = login_session(&client).await;
+    access_mycqu(&client, &mut session).await.unwrap();
+    let remaining = session.mycqu_access_remaining().unwrap();
+    assert!(remaining > Duration::from_secs(3500) && remaining <= Duration::from_secs(3600));
+    let access_info = session.access_infos().mycqu_access_info.clone().unwrap();
+    assert!(access_info.can_refresh());
+
+    // 令牌过期后通过refresh_token刷新，无需统一身份认证的登陆状态
+    server.expire_tokens();
+    logout(&client, &mut session).await.unwrap();
+    refresh_mycqu(&client, &mut session).await.unwrap();
+    let refreshed = session.access_infos().mycqu_access_info.clone().unwrap();
+    assert_ne!(refreshed, access_info);
+    User::fetch_self(&client, &session).await.unwrap();
+
+    // refresh_token失效时回退为重新获取访问权限
+    server.revoke_refresh_tokens();
+    let res = refresh_mycqu(&client, &mut session).await;
+    assert!(matches!(res.unwrap_err(), ApiError::NotLogin));
+
+    let mut session = login_session(&client)
+        .await
+        .set_access_infos(session.access_infos().clone());
+    refresh_mycqu(&client, &mut session).await.unwrap();
+    User::fetch_self(&client, &session).await.unwrap();
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_mock_cqu_client() {
+    let server = MockServer::start().await.unwrap();
+    let client = CquClient::new(DEFAULT_AUTH, DEFAULT_PASSWORD).set_client(server.client());
+
+    // 第一次调用时自动登陆并获取访问权限
+    assert!(!client.session().await.is_login());
+    let scores = client.scores(false).await.unwrap();
+    assert!(!scores.is_empty());
+    assert!(
+        !client
+            .score_detail(&scores[0])
+            .await
+            .unwrap()
+            .components
+            .is_empty()
+    );
+    let session = client.session().await;
+    assert!(session.is_login());
+    assert_eq!(session.services(), vec![Service::MyCQU]);
+
+    assert_eq!(client.user().await.unwrap().code, DEFAULT_STUDENT_ID);
+    assert!(!client.exams().await.unwrap().is_empty());
+    client.gpa_ranking(false).await.unwrap();
+    assert!(client.gpa_ranking_report().await.unwrap().minor.is_some());
+    let curr = client.current_session_info().await.unwrap();
+    client.timetable(curr.session.id.unwrap()).await.unwrap();
+
+    client.card_balance().await.unwrap();
+    client
+        .card_bills("2023-11-10", "2023-12-12", 1, 100)
+        .await
+        .unwrap();
+    client.energy_fees("b5321", true).await.unwrap();
+    assert_eq!(client.session().await.services().len(), 2);
+
+    // 访问权限失效时自动重新认证，新的访问信息保存在客户端中
+    let access_infos = client.session().await.access_infos().clone();
+    server.expire_tokens();
+    client.scores(false).await.unwrap();
+    client.energy_fees("b5321", true).await.unwrap();
+    client
+        .card_bills("2023-11-10", "2023-12-12", 1, 100)
+        .await
+        .unwrap();
+    let refreshed = client.session().await.access_infos().clone();
+    assert_ne!(refreshed.mycqu(), access_infos.mycqu());
+    assert_ne!(
+        refreshed.card().unwrap().synjones_auth(),
+        access_infos.card().unwrap().synjones_auth()
+    );
+
+    let client = CquClient::new(DEFAULT_AUTH, "wrong_password").set_client(server.client());
+    assert!(matches!(
+        client.card().await.unwrap_err(),
+        ClientError::LoginFailed {
+            result: LoginResult::IncorrectLoginCredentials
+        }
+    ));
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_mock_access_all() {
+    let server = MockServer::start().await.unwrap();
+    let client = server.client();
+
+    let report = access_all(&client, &mut Session::new()).await;
+    assert!(matches!(report.mycqu, Err(ApiError::NotLogin)));
+    assert!(matches!(report.card, Err(ApiError::NotLogin)));
+    assert_eq!(report.failed(), vec![Service::MyCQU, Service::Card]);
+
+    let mut session = login_session(&client).await;
+    server.fail_next("/authserver/oauth/token", MockFailure::Status(500));
+    let report = access_all(&client, &mut session).await;
+    assert!(!report.all_succeeded());
+    assert_eq!(report.succeeded(), vec![Service::Card]);
+    assert_eq!(session.services(), vec![Service::Card]);
+
+    let report = access_all(&client, &mut session).await;
+    assert!(report.all_succeeded());
+    assert_eq!(session.services(), vec![Service::MyCQU, Service::Card]);
+    User::fetch_self(&client, &session).await.unwrap();
+    Card::fetch_self(&client, &session).await.unwrap();
+
+    let client = CquClient::new(DEFAULT_AUTH, DEFAULT_PASSWORD).set_client(server.client());
+    assert!(client.access_all().await.unwrap().all_succeeded());
+}
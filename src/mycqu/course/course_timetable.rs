@@ -10,7 +10,7 @@ use crate::{
     session::{Client, Session},
     utils::{
         ApiModel,
-        consts::{MYCQU_API_ENROLL_TIMETABLE_URL, MYCQU_API_TIMETABLE_URL},
+        consts::{MYCQU_API_ENROLL_TIMETABLE_PATH, MYCQU_API_TIMETABLE_PATH},
         datetimes::WeekStrHelper,
         models::Period,
         response_json_map,
@@ -91,7 +91,7 @@ impl CourseTimetable {
     ) -> MyCQUResult<Vec<Self>> {
         let response = mycqu_request_handler(client, session, |client| {
             client
-                .post(MYCQU_API_TIMETABLE_URL)
+                .post(client.endpoints().mycqu_url(MYCQU_API_TIMETABLE_PATH))
                 .query(&[("sessionId", cqu_session_id)])
                 .json(&vec![student_id.as_ref()])
         })
@@ -128,7 +128,9 @@ impl CourseTimetable {
         let response = mycqu_request_handler(client, session, |client| {
             client.get(format!(
                 "{}/{}",
-                MYCQU_API_ENROLL_TIMETABLE_URL,
+                client
+                    .endpoints()
+                    .mycqu_url(MYCQU_API_ENROLL_TIMETABLE_PATH),
                 student_id.as_ref()
             ))
         })
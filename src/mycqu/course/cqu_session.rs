@@ -12,7 +12,7 @@ use crate::{
     },
     mycqu::utils::mycqu_request_handler,
     session::{Client, Session},
-    utils::{ApiModel, consts::MYCQU_API_SESSION_URL},
+    utils::{ApiModel, consts::MYCQU_API_SESSION_PATH},
 };
 
 /// 重庆大学的某一学期
@@ -178,16 +178,16 @@ impl CQUSession {
         #[derive(Serialize, Deserialize)]
         struct LocalCQUSessionHelper(#[serde_as(deserialize_as = "CQUSessionHelper")] CQUSession);
 
-        Ok(
-            mycqu_request_handler(client, session, |client| client.get(MYCQU_API_SESSION_URL))
-                .await?
-                .json::<Vec<LocalCQUSessionHelper>>()
-                .await?
-                .into_iter()
-                .map(|item| item.0)
-                .filter(|item| item.id.is_some())
-                .collect::<Vec<_>>(),
-        )
+        Ok(mycqu_request_handler(client, session, |client| {
+            client.get(client.endpoints().mycqu_url(MYCQU_API_SESSION_PATH))
+        })
+        .await?
+        .json::<Vec<LocalCQUSessionHelper>>()
+        .await?
+        .into_iter()
+        .map(|item| item.0)
+        .filter(|item| item.id.is_some())
+        .collect::<Vec<_>>())
     }
 }
 
@@ -14,8 +14,8 @@ use crate::{
     utils::{
         ApiModel,
         consts::{
-            MYCQU_API_ALL_SESSION_INFO_URL, MYCQU_API_CURR_SESSION_INFO_URL,
-            MYCQU_API_SESSION_INFO_DETAIL_URL,
+            MYCQU_API_ALL_SESSION_INFO_PATH, MYCQU_API_CURR_SESSION_INFO_PATH,
+            MYCQU_API_SESSION_INFO_DETAIL_PATH,
         },
         response_json_map,
     },
@@ -118,7 +118,11 @@ impl CQUSessionInfo {
     /// ```
     pub async fn fetch_all(client: &Client, session: &Session) -> MyCQUResult<Vec<Self>> {
         let response = mycqu_request_handler(client, session, |client| {
-            client.get(MYCQU_API_ALL_SESSION_INFO_URL)
+            client.get(
+                client
+                    .endpoints()
+                    .mycqu_url(MYCQU_API_ALL_SESSION_INFO_PATH),
+            )
         })
         .await?;
         let (mut res, raw_response) = response_json_map(response).await?;
@@ -146,7 +150,11 @@ impl CQUSessionInfo {
     /// ```
     pub async fn fetch_curr(client: &Client, session: &Session) -> MyCQUResult<Self> {
         let response = mycqu_request_handler(client, session, |client| {
-            client.get(MYCQU_API_CURR_SESSION_INFO_URL)
+            client.get(
+                client
+                    .endpoints()
+                    .mycqu_url(MYCQU_API_CURR_SESSION_INFO_PATH),
+            )
         })
         .await?;
         let (mut res, raw_response) = response_json_map(response).await?;
@@ -177,7 +185,10 @@ impl CQUSessionInfo {
         let response = mycqu_request_handler(client, session, |client| {
             client.get(format!(
                 "{}/{}",
-                MYCQU_API_SESSION_INFO_DETAIL_URL, session_id
+                client
+                    .endpoints()
+                    .mycqu_url(MYCQU_API_SESSION_INFO_DETAIL_PATH),
+                session_id
             ))
         })
         .await?;
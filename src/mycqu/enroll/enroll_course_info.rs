@@ -15,7 +15,7 @@ use crate::{
         utils::{check_website_response, mycqu_request_handler},
     },
     session::{Client, Session},
-    utils::{ApiModel, consts::MYCQU_API_ENROLL_COURSE_LIST_URL, response_json_map},
+    utils::{ApiModel, consts::MYCQU_API_ENROLL_COURSE_LIST_PATH, response_json_map},
 };
 
 /// 可选课程信息
@@ -74,7 +74,11 @@ impl EnrollCourseInfo {
     ) -> MyCQUResult<HashMap<String, Vec<Self>>> {
         let response = mycqu_request_handler(client, session, |client| {
             client
-                .get(MYCQU_API_ENROLL_COURSE_LIST_URL)
+                .get(
+                    client
+                        .endpoints()
+                        .mycqu_url(MYCQU_API_ENROLL_COURSE_LIST_PATH),
+                )
                 .query(&[("selectionSource", if is_major { "主修" } else { "辅修" })])
         })
         .await?;
@@ -14,7 +14,7 @@ use crate::{
         utils::mycqu_request_handler,
     },
     session::{Client, Session},
-    utils::{ApiModel, consts::MYCQU_API_ENROLL_COURSE_DETAIL_URL, response_json_map},
+    utils::{ApiModel, consts::MYCQU_API_ENROLL_COURSE_DETAIL_PATH, response_json_map},
 };
 
 /// 可选具体课程，包含课程上课时间、上课教师、教室可容纳学生等信息
@@ -93,7 +93,9 @@ impl EnrollCourseItem {
             client
                 .get(format!(
                     "{}/{}",
-                    MYCQU_API_ENROLL_COURSE_DETAIL_URL,
+                    client
+                        .endpoints()
+                        .mycqu_url(MYCQU_API_ENROLL_COURSE_DETAIL_PATH),
                     course_id.as_ref()
                 ))
                 .query(&[("selectionSource", if is_major { "主修" } else { "辅修" })])
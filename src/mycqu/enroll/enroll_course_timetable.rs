@@ -53,8 +53,8 @@ serde_conv!(
         }
         result
     },
-    |timetable: &str| -> Result<_, std::convert::Infallible> {
-        Ok(EnrollCourseTimetable::parse_timetable_str(timetable))
+    |timetable: String| -> Result<_, std::convert::Infallible> {
+        Ok(EnrollCourseTimetable::parse_timetable_str(&timetable))
     }
 );
 
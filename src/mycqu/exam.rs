@@ -9,7 +9,7 @@ use crate::{
         utils::{encrypt::encrypt_student_id, mycqu_request_handler},
     },
     session::{Client, Session},
-    utils::{ApiModel, consts::MYCQU_API_EXAM_LIST_URL, response_json_map},
+    utils::{ApiModel, consts::MYCQU_API_EXAM_LIST_PATH, response_json_map},
 };
 
 /// 监考员信息
@@ -122,8 +122,8 @@ impl Exam {
     ) -> MyCQUResult<Vec<Exam>> {
         let response = mycqu_request_handler(client, session, |client| {
             client
-                .get(MYCQU_API_EXAM_LIST_URL)
-                .query(&[("studentId", encrypt_student_id(student_id))])
+                .get(client.endpoints().mycqu_url(MYCQU_API_EXAM_LIST_PATH))
+                .query(&[("studentId", encrypt_student_id(student_id.as_ref()))])
         })
         .await?;
         let (mut res, raw_response) = response_json_map(response).await?;
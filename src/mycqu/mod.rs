@@ -6,12 +6,15 @@ use snafu::{ensure, whatever};
 use crate::{
     errors,
     errors::mycqu::MyCQUResult,
-    mycqu::utils::{access::get_oauth_token, mycqu_request_handler},
-    session::{Client, Session, access_info::MyCQUAccessInfo},
+    mycqu::utils::{
+        access::{get_oauth_token, refresh_oauth_token},
+        mycqu_request_handler,
+    },
+    session::{Client, Session},
     sso::access_services,
     utils::{
         ApiModel,
-        consts::{MYCQU_API_USER_URL, MYCQU_SERVICE_URL},
+        consts::{MYCQU_API_USER_PATH, MYCQU_SERVICE_PATH},
     },
 };
 
@@ -30,17 +33,57 @@ pub async fn access_mycqu(client: &Client, session: &mut Session) -> MyCQUResult
 
     // access_services 只会因为网络原因产生异常，不会产生任何`SSOError`
     whatever!(
-        access_services(client, session, MYCQU_SERVICE_URL).await,
+        access_services(
+            client,
+            session,
+            client.endpoints().mycqu_url(MYCQU_SERVICE_PATH)
+        )
+        .await,
         "Unexpected SSOError happened"
     );
 
-    let auth_token = get_oauth_token(client, session).await?;
-    session.access_infos.mycqu_access_info = Some(MyCQUAccessInfo {
-        auth_header: auth_token,
-    });
+    let access_info = get_oauth_token(client, session).await?;
+    session.access_infos.get_mut().mycqu_access_info = Some(access_info);
     Ok(())
 }
 
+/// 刷新教务网`my.cqu.edu.cn`的访问令牌
+///
+/// 会话中保存了`refresh_token`时优先使用其换取新的令牌，没有`refresh_token`或刷新失败时回退到[`access_mycqu`]重新获取权限，
+/// 此时要求会话已通过[`sso`](crate::sso)登陆
+///
+/// # Examples
+/// ```rust, no_run
+/// # use std::time::Duration;
+/// # use rsmycqu::mycqu::refresh_mycqu;
+/// # use rsmycqu::session::{Client, Session};
+/// # async fn refresh(client: &Client, session: &mut Session) {
+/// if session
+///     .mycqu_access_remaining()
+///     .is_some_and(|remaining| remaining < Duration::from_secs(300))
+/// {
+///     refresh_mycqu(client, session).await.unwrap();
+/// }
+/// # }
+/// ```
+pub async fn refresh_mycqu(client: &Client, session: &mut Session) -> MyCQUResult<()> {
+    let refresh_token = session
+        .access_infos
+        .read()
+        .mycqu_access_info
+        .as_ref()
+        .and_then(|access_info| access_info.refresh_token.clone());
+
+    if let Some(refresh_token) = refresh_token
+        && let Ok(access_info) = refresh_oauth_token(client, session, &refresh_token).await
+    {
+        session.access_infos.get_mut().mycqu_access_info = Some(access_info);
+        return Ok(());
+    }
+
+    access_mycqu(client, session).await
+}
+
 /// 教务网用户信息接口响应数据模型
 #[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
 pub struct User {
@@ -81,8 +124,10 @@ impl User {
     /// # }
     /// ```
     pub async fn fetch_self(client: &Client, session: &Session) -> MyCQUResult<Self> {
-        let res =
-            mycqu_request_handler(client, session, |client| client.get(MYCQU_API_USER_URL)).await?;
+        let res = mycqu_request_handler(client, session, |client| {
+            client.get(client.endpoints().mycqu_url(MYCQU_API_USER_PATH))
+        })
+        .await?;
 
         Ok(res.json::<Self>().await?)
     }
@@ -0,0 +1,144 @@
+//! 成绩的组成明细
+
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+use crate::{
+    errors::mycqu::{MyCQUError, MyCQUResult},
+    mycqu::{
+        score::{Score, ScoreValue},
+        utils::{check_website_response, mycqu_request_handler},
+    },
+    session::{Client, Session},
+    utils::{ApiModel, consts::MYCQU_API_SCORE_DETAIL_PATH, response_json_map},
+};
+
+/// 成绩组成部分的类型
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum ScoreComponentKind {
+    /// 平时成绩
+    Usual,
+    /// 期中成绩
+    Midterm,
+    /// 期末成绩
+    Final,
+    /// 实验成绩
+    Lab,
+    /// 其他成绩
+    Other,
+}
+
+/// 成绩的一个组成部分，如平时成绩、期末成绩等
+#[serde_as]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ScoreComponent {
+    /// 组成部分的名称，如`平时成绩`
+    #[serde(alias = "itemName")]
+    pub name: String,
+    /// 在总评成绩中所占的百分比，如`30.0`表示占30%
+    #[serde_as(
+        deserialize_as = "serde_with::DefaultOnError<Option<serde_with::PickFirst<(_, serde_with::DisplayFromStr)>>>"
+    )]
+    #[serde(alias = "proportion", default)]
+    pub weight: Option<f64>,
+    /// 该部分的成绩，尚未录入时为`None`
+    #[serde_as(
+        deserialize_as = "serde_with::DefaultOnError<Option<serde_with::PickFirst<(_, NumericScore)>>>"
+    )]
+    #[serde(default)]
+    pub score: Option<String>,
+}
+
+impl ScoreComponent {
+    /// 由名称判断组成部分的类型
+    pub fn kind(&self) -> ScoreComponentKind {
+        match self.name.as_str() {
+            name if name.contains("平时") => ScoreComponentKind::Usual,
+            name if name.contains("期中") => ScoreComponentKind::Midterm,
+            name if name.contains("期末") => ScoreComponentKind::Final,
+            name if name.contains("实验") => ScoreComponentKind::Lab,
+            _ => ScoreComponentKind::Other,
+        }
+    }
+
+    /// 解析后的成绩，见[`ScoreValue`]
+    pub fn value(&self) -> Option<ScoreValue> {
+        self.score.as_deref().and_then(|score| score.parse().ok())
+    }
+}
+
+/// 成绩明细对象，由[`Score::fetch_detail`]获取
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ScoreDetail {
+    /// 对应成绩的ID，与[`Score::id`]相同，响应中没有ID时为`None`
+    #[serde(alias = "id", default)]
+    pub score_id: Option<String>,
+    /// 总评成绩
+    #[serde(alias = "effectiveScoreShow", default)]
+    pub total: Option<String>,
+    /// 成绩的各组成部分
+    #[serde(alias = "scoreDetails", default)]
+    pub components: Vec<ScoreComponent>,
+}
+
+impl ScoreDetail {
+    /// 类型为`kind`的组成部分
+    pub fn component(&self, kind: ScoreComponentKind) -> Option<&ScoreComponent> {
+        self.components
+            .iter()
+            .find(|component| component.kind() == kind)
+    }
+}
+
+impl ApiModel for ScoreDetail {}
+
+serde_with::serde_conv!(
+    NumericScore,
+    String,
+    |score: &String| score.clone(),
+    |score: f64| -> Result<_, std::convert::Infallible> { Ok(score.to_string()) }
+);
+
+impl Score {
+    /// 通过具有教务网权限的会话([`Session`])，获取该成绩的组成明细([`ScoreDetail`])
+    ///
+    /// 成绩没有ID时（如手动构造的成绩）会抛出[`MyCQUError::MissingScoreId`]异常
+    ///
+    /// 成绩明细接口`/api/sam/score/student/score-detail`及其响应格式尚未经真实的教务网响应验证，
+    /// [`ScoreDetail`]的字段均可缺失并同时接受数字与字符串形式的成绩，但接口与预期不符时仍可能无法获取明细
+    ///
+    /// # Examples
+    /// ```rust, no_run
+    /// # use rsmycqu::mycqu::access_mycqu;
+    /// # use rsmycqu::mycqu::score::{Score, ScoreComponentKind};
+    /// # use rsmycqu::session::{Client, Session};
+    /// # use rsmycqu::sso::login;
+    /// #
+    /// # async fn fetch_score_detail() {
+    /// # let client = Client::default();
+    /// # let mut session = Session::new();
+    /// login(&client, &mut session, "your_auth", "your_password", false).await.unwrap();
+    /// access_mycqu(&client, &mut session).await.unwrap();
+    /// let scores = Score::fetch_self(&client, &session, false).await.unwrap();
+    /// let detail = scores[0].fetch_detail(&client, &session).await.unwrap();
+    /// let usual = detail.component(ScoreComponentKind::Usual);
+    /// # }
+    /// ```
+    pub async fn fetch_detail(
+        &self,
+        client: &Client,
+        session: &Session,
+    ) -> MyCQUResult<ScoreDetail> {
+        let id = self.id.as_deref().ok_or(MyCQUError::MissingScoreId)?;
+        let response = mycqu_request_handler(client, session, |client| {
+            client
+                .get(client.endpoints().mycqu_url(MYCQU_API_SCORE_DETAIL_PATH))
+                .query(&[("id", id)])
+        })
+        .await?;
+        let (mut res, raw_response) = response_json_map(response).await?;
+        check_website_response(&res)?;
+
+        ScoreDetail::extract_object(&mut res, "data", &raw_response)
+    }
+}
@@ -0,0 +1,236 @@
+//! 基于已获取成绩的离线绩点计算
+
+use crate::mycqu::{
+    course::CQUSession,
+    score::{Score, ScoreValue},
+};
+
+/// 重修课程的修读性质
+const RETAKE: &str = "重修";
+
+/// 将成绩字符串换算为绩点，换算规则见[`ScoreValue::grade_point`]
+///
+/// `缺考`、`作弊`按0绩点计入；`通过`、`不通过`等二级制成绩、`缓考`、`免修`及无法识别的成绩不计入绩点，返回`None`
+///
+/// # Examples
+/// ```rust
+/// # use rsmycqu::mycqu::score::grade_point;
+/// assert_eq!(grade_point("92"), Some(4.0));
+/// assert_eq!(grade_point("良"), Some(3.7));
+/// assert_eq!(grade_point("通过"), None);
+/// ```
+pub fn grade_point(score: &str) -> Option<f64> {
+    score.parse::<ScoreValue>().ok()?.grade_point()
+}
+
+impl Score {
+    /// 该成绩对应的绩点，见[`grade_point`]
+    pub fn grade_point(&self) -> Option<f64> {
+        self.value()?.grade_point()
+    }
+
+    /// 是否为重修成绩，即[`Score::study_nature`]为`重修`
+    ///
+    /// [`GPACalculator`]计算总体绩点时，重修成绩与同一课程的其他修读只计入绩点最高的一次；
+    /// 未标记为重修的多次修读（如分学期开设的同一课程）各自计入
+    pub fn is_retake(&self) -> bool {
+        self.study_nature == RETAKE
+    }
+
+    /// 用于识别同一课程多次修读的键，优先使用课程代码
+    pub(super) fn course_key(&self) -> Option<&str> {
+        self.course.code.as_deref().or(self.course.name.as_deref())
+    }
+}
+
+/// 一组成绩的绩点统计
+#[derive(Debug, Clone, PartialEq)]
+pub struct GPASummary {
+    /// 平均学分绩点，没有计入绩点的课程时为`None`
+    pub gpa: Option<f64>,
+    /// 百分制成绩的加权平均分，没有百分制成绩时为`None`
+    pub weighted_avg: Option<f64>,
+    /// 计入绩点的总学分
+    pub credits: f64,
+    /// 计入绩点的课程数
+    pub courses: usize,
+}
+
+impl GPASummary {
+    fn from_scores<'a>(scores: impl IntoIterator<Item = &'a Score>) -> Self {
+        let mut credits = 0.0;
+        let mut points = 0.0;
+        let mut courses = 0;
+        let mut numeric_credits = 0.0;
+        let mut numeric_total = 0.0;
+
+        for score in scores {
+            let Some(credit) = score.course.credit.filter(|credit| *credit > 0.0) else {
+                continue;
+            };
+            let Some(value) = score.value() else {
+                continue;
+            };
+            let Some(point) = value.grade_point() else {
+                continue;
+            };
+
+            credits += credit;
+            points += point * credit;
+            courses += 1;
+            if let Some(numeric) = value.as_numeric() {
+                numeric_credits += credit;
+                numeric_total += numeric * credit;
+            }
+        }
+
+        GPASummary {
+            gpa: (credits > 0.0).then(|| points / credits),
+            weighted_avg: (numeric_credits > 0.0).then(|| numeric_total / numeric_credits),
+            credits,
+            courses,
+        }
+    }
+}
+
+/// 单个学期的绩点统计
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionGPA {
+    /// 学期
+    pub session: CQUSession,
+    /// 该学期的绩点统计
+    pub summary: GPASummary,
+}
+
+/// [`GPACalculator::calculate`]的计算结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct GPAReport {
+    /// 全部学期的绩点统计，重修成绩与该课程的其他修读只计入绩点最高的一次，见[`Score::is_retake`]
+    pub overall: GPASummary,
+    /// 按学期先后排列的各学期绩点统计，各学期计入该学期实际修读的全部成绩
+    pub sessions: Vec<SessionGPA>,
+}
+
+/// 基于已获取成绩的离线绩点计算器
+///
+/// 可筛选参与计算的课程（如仅计算必修课），或替换、添加成绩以预估出分后的绩点
+///
+/// # Examples
+/// ```rust, no_run
+/// # use rsmycqu::mycqu::score::{GPACalculator, Score};
+/// # use rsmycqu::session::{Client, Session};
+/// # async fn gpa(client: &Client, session: &Session) {
+/// let scores = Score::fetch_self(client, session, false).await.unwrap();
+/// let calculator = GPACalculator::new(scores);
+/// let report = calculator.calculate();
+/// println!("{:?}", report.overall.gpa);
+///
+/// // 仅计算必修课
+/// let required = calculator.clone().filter(|score| score.course_nature == "必修");
+/// // 预估尚未出分的课程取得85分后的绩点
+/// let what_if = calculator.set_score("MATH10821", "85").calculate();
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct GPACalculator {
+    scores: Vec<Score>,
+}
+
+impl GPACalculator {
+    /// 由成绩构建计算器
+    pub fn new(scores: impl IntoIterator<Item = Score>) -> Self {
+        GPACalculator {
+            scores: scores.into_iter().collect(),
+        }
+    }
+
+    /// 仅保留满足`predicate`的成绩
+    pub fn filter(self, predicate: impl Fn(&Score) -> bool) -> Self {
+        GPACalculator {
+            scores: self
+                .scores
+                .into_iter()
+                .filter(|score| predicate(score))
+                .collect(),
+        }
+    }
+
+    /// 将课程代码为`course_code`的成绩设置为`score`，同一课程多次修读时设置最近一次修读的成绩
+    ///
+    /// 可用于预估尚未出分的课程取得某一成绩后的绩点，不存在该课程时不做任何改变
+    pub fn set_score(mut self, course_code: &str, score: impl Into<String>) -> Self {
+        if let Some(target) = self
+            .scores
+            .iter_mut()
+            .filter(|item| item.course.code.as_deref() == Some(course_code))
+            .max_by_key(|item| session_order(&item.session))
+        {
+            target.score = Some(score.into());
+        }
+        self
+    }
+
+    /// 添加一条成绩，如预估中的新课程成绩
+    pub fn add_score(mut self, score: Score) -> Self {
+        self.scores.push(score);
+        self
+    }
+
+    /// 参与计算的成绩
+    pub fn scores(&self) -> &[Score] {
+        &self.scores
+    }
+
+    /// 计算总体与各学期的绩点
+    pub fn calculate(&self) -> GPAReport {
+        let mut sessions: Vec<CQUSession> = Vec::new();
+        for score in &self.scores {
+            if !sessions.contains(&score.session) {
+                sessions.push(score.session.clone());
+            }
+        }
+        sessions.sort_by_key(session_order);
+
+        GPAReport {
+            overall: GPASummary::from_scores(self.best_attempts()),
+            sessions: sessions
+                .into_iter()
+                .map(|session| SessionGPA {
+                    summary: GPASummary::from_scores(
+                        self.scores.iter().filter(|score| score.session == session),
+                    ),
+                    session,
+                })
+                .collect(),
+        }
+    }
+
+    /// 计入总体绩点的成绩：重修成绩与同一课程的其他修读取绩点最高的一次，绩点相同时取较晚的一次
+    fn best_attempts(&self) -> Vec<&Score> {
+        let rank = |score: &Score| {
+            (
+                score.grade_point().unwrap_or(f64::NEG_INFINITY),
+                session_order(&score.session),
+            )
+        };
+
+        let (retakes, mut best): (Vec<&Score>, Vec<&Score>) =
+            self.scores.iter().partition(|score| score.is_retake());
+        for score in retakes {
+            let existing = score
+                .course_key()
+                .and_then(|key| best.iter().position(|item| item.course_key() == Some(key)));
+            match existing {
+                Some(index) if rank(score) >= rank(best[index]) => best[index] = score,
+                Some(_) => {}
+                None => best.push(score),
+            }
+        }
+        best
+    }
+}
+
+/// 学期的先后顺序，同一年份中春季学期早于秋季学期
+fn session_order(session: &CQUSession) -> (u16, bool) {
+    (session.year, session.is_autumn)
+}
@@ -1,8 +1,12 @@
-//! 该模块提供成绩查询、绩点查询接口
+//! 该模块提供成绩查询、成绩明细查询、绩点查询接口，以及基于已获取成绩的离线绩点计算与成绩变化监听
 
+pub use detail::*;
+pub use gpa::*;
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{Map, Value};
 use serde_with::serde_as;
+pub use value::*;
+pub use watcher::*;
 
 use crate::{
     errors,
@@ -14,15 +18,27 @@ use crate::{
     session::{Client, Session},
     utils::{
         ApiModel,
-        consts::{MYCQU_API_GPA_RANKING_URL, MYCQU_API_SCORE_URL},
+        consts::{MYCQU_API_GPA_RANKING_PATH, MYCQU_API_SCORE_PATH},
         response_json_map,
     },
 };
 
+mod detail;
+mod gpa;
+mod value;
+mod watcher;
+
 /// 成绩对象
+///
+/// 自加入[`Score::id`]起标记为`#[non_exhaustive]`，本库外无法再以结构体字面量构造或完整解构，
+/// 请使用[`Score::new`]构造，之后添加字段时将不再破坏兼容性
 #[serde_as]
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct Score {
+    /// 成绩ID，用于获取成绩明细，见[`Score::fetch_detail`]
+    #[serde(default)]
+    pub id: Option<String>,
     /// 学期
     #[serde(alias = "sessionName")]
     #[serde_as(deserialize_as = "serde_with::PickFirst<(_, serde_with::DisplayFromStr)>")]
@@ -30,7 +46,7 @@ pub struct Score {
     /// 课程
     #[serde(flatten)]
     pub course: Course,
-    /// 成绩，可能为数字，也可能为字符（优、良等）
+    /// 成绩，可能为数字，也可能为字符（优、良等），可通过[`Score::value`]获取解析后的成绩
     #[serde_as(deserialize_as = "serde_with::FromInto<ScoreField>")]
     #[serde(flatten)]
     pub score: Option<String>,
@@ -55,6 +71,49 @@ serde_fallback!(
 );
 
 impl Score {
+    /// 构造没有成绩ID的成绩，如用于[`GPACalculator::add_score`]预估新课程的绩点
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use rsmycqu::mycqu::course::Course;
+    /// # use rsmycqu::mycqu::score::Score;
+    /// let course = Course {
+    ///     name: Some("高等数学".to_string()),
+    ///     code: Some("MATH10821".to_string()),
+    ///     course_num: None,
+    ///     dept: None,
+    ///     credit: Some(6.0),
+    ///     instructor: None,
+    ///     session: None,
+    /// };
+    /// let score = Score::new("2023秋".parse().unwrap(), course, Some("92".to_string()), "初修", "必修");
+    /// assert_eq!(score.id, None);
+    /// ```
+    pub fn new(
+        session: CQUSession,
+        course: Course,
+        score: Option<String>,
+        study_nature: impl Into<String>,
+        course_nature: impl Into<String>,
+    ) -> Self {
+        Score {
+            id: None,
+            session,
+            course,
+            score,
+            study_nature: study_nature.into(),
+            course_nature: course_nature.into(),
+        }
+    }
+
+    /// 设置成绩ID
+    pub fn set_id(self, id: impl Into<String>) -> Self {
+        Score {
+            id: Some(id.into()),
+            ..self
+        }
+    }
+
     /// 通过具有教务网权限的会话([`Session`])，获取成绩([`Vec<Score>`])
     ///
     /// # Examples
@@ -82,7 +141,7 @@ impl Score {
     ) -> MyCQUResult<Vec<Self>> {
         let response = mycqu_request_handler(client, session, |client| {
             client
-                .get(MYCQU_API_SCORE_URL)
+                .get(client.endpoints().mycqu_url(MYCQU_API_SCORE_PATH))
                 .query(&[("isMinorBoo", is_minor)])
         })
         .await?;
@@ -151,6 +210,8 @@ pub struct GPARanking {
 impl GPARanking {
     /// 通过具有教务网权限的会话([`Session`])，获取绩点排名([`GPARanking`])
     ///
+    /// `is_minor`为`true`时获取辅修专业的绩点排名
+    ///
     /// # Examples
     /// ```rust, no_run
     /// # use serde::de::Unexpected::Option;
@@ -166,21 +227,104 @@ impl GPARanking {
     /// let cqu_session = CQUSession{ id: Some(1234), year: 2023, is_autumn: true};
     /// login(&client, &mut session, "your_auth", "your_password", false).await.unwrap();
     /// access_mycqu(&client, &mut session).await.unwrap();
-    /// let user = GPARanking::fetch_self(&client, &session);
+    /// let user = GPARanking::fetch_self(&client, &session, false);
     /// # }
     /// ```
-    pub async fn fetch_self(client: &Client, session: &Session) -> MyCQUResult<Self> {
+    pub async fn fetch_self(
+        client: &Client,
+        session: &Session,
+        is_minor: bool,
+    ) -> MyCQUResult<Self> {
+        let (mut res, raw_response) = Self::request(client, session, is_minor).await?;
+        Self::extract_object(&mut res, "data", &raw_response)
+    }
+
+    /// 获取绩点排名，`data`为空（如未修读辅修专业）时返回`None`
+    async fn fetch_optional(
+        client: &Client,
+        session: &Session,
+        is_minor: bool,
+    ) -> MyCQUResult<Option<Self>> {
+        let (mut res, raw_response) = Self::request(client, session, is_minor).await?;
+        if res.get("data").is_none_or(Value::is_null) {
+            return Ok(None);
+        }
+        Self::extract_object(&mut res, "data", &raw_response).map(Some)
+    }
+
+    async fn request(
+        client: &Client,
+        session: &Session,
+        is_minor: bool,
+    ) -> MyCQUResult<(Map<String, Value>, String)> {
         let response = mycqu_request_handler(client, session, |client| {
             client
-                .get(MYCQU_API_GPA_RANKING_URL)
-                .query(&[("isMinorBoo", false)])
+                .get(client.endpoints().mycqu_url(MYCQU_API_GPA_RANKING_PATH))
+                .query(&[("isMinorBoo", is_minor)])
         })
         .await?;
-        let (mut res, raw_response) = response_json_map(response).await?;
+        let (res, raw_response) = response_json_map(response).await?;
         check_website_response(&res)?;
 
-        Self::extract_object(&mut res, "data", &raw_response)
+        Ok((res, raw_response))
     }
 }
 
 impl ApiModel for GPARanking {}
+
+/// 主修与辅修专业的绩点排名
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct GPARankingReport {
+    /// 主修专业的绩点排名
+    pub major: GPARanking,
+    /// 辅修专业的绩点排名，未修读辅修专业时为`None`
+    pub minor: Option<GPARanking>,
+}
+
+impl GPARankingReport {
+    /// 通过具有教务网权限的会话([`Session`])，同时获取主修与辅修专业的绩点排名([`GPARankingReport`])
+    ///
+    /// # Examples
+    /// ```rust, no_run
+    /// # use rsmycqu::mycqu::access_mycqu;
+    /// # use rsmycqu::mycqu::score::GPARankingReport;
+    /// # use rsmycqu::session::{Client, Session};
+    /// # use rsmycqu::sso::login;
+    /// #
+    /// # async fn fetch_gpa_ranking_report() {
+    /// # let client = Client::default();
+    /// # let mut session = Session::new();
+    /// login(&client, &mut session, "your_auth", "your_password", false).await.unwrap();
+    /// access_mycqu(&client, &mut session).await.unwrap();
+    /// let report = GPARankingReport::fetch_self(&client, &session).await.unwrap();
+    /// println!("{} {:?}", report.major.gpa, report.minor_gpa());
+    /// # }
+    /// ```
+    pub async fn fetch_self(client: &Client, session: &Session) -> MyCQUResult<Self> {
+        let (major, minor) = tokio::join!(
+            GPARanking::fetch_self(client, session, false),
+            GPARanking::fetch_optional(client, session, true)
+        );
+
+        Ok(GPARankingReport {
+            major: major?,
+            minor: minor?,
+        })
+    }
+
+    /// 辅修绩点，优先取自辅修专业的绩点排名
+    pub fn minor_gpa(&self) -> Option<f32> {
+        self.minor
+            .as_ref()
+            .map(|minor| minor.gpa)
+            .or(self.major.minor_gpa)
+    }
+
+    /// 辅修加权平均分，优先取自辅修专业的绩点排名
+    pub fn minor_weighted_avg(&self) -> Option<f32> {
+        self.minor
+            .as_ref()
+            .map(|minor| minor.weighted_avg)
+            .or(self.major.minor_weighted_avg)
+    }
+}
@@ -0,0 +1,310 @@
+//! 成绩的类型化表示
+
+use std::{cmp::Ordering, fmt::Display, str::FromStr};
+
+use serde_with::{DeserializeFromStr, SerializeDisplay};
+
+use crate::{
+    errors::{ApiError, mycqu::MyCQUError},
+    mycqu::score::Score,
+};
+
+/// 百分制成绩的绩点下限，按分数从高到低排列
+///
+/// 取自重庆大学教务处（<https://jwc.cqu.edu.cn>）发布的本科生学业成绩管理规定中的成绩与绩点对照表
+const NUMERIC_GRADE_POINTS: [(f64, f64); 9] = [
+    (90.0, 4.0),
+    (85.0, 3.7),
+    (82.0, 3.3),
+    (78.0, 3.0),
+    (75.0, 2.7),
+    (72.0, 2.3),
+    (68.0, 2.0),
+    (64.0, 1.5),
+    (60.0, 1.0),
+];
+
+/// 按[`NUMERIC_GRADE_POINTS`]将百分制成绩换算为绩点
+fn numeric_grade_point(score: f64) -> f64 {
+    NUMERIC_GRADE_POINTS
+        .iter()
+        .find(|(lower, _)| score >= *lower)
+        .map_or(0.0, |(_, point)| *point)
+}
+
+/// 五级制成绩，按等级从低到高排序
+#[derive(
+    Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, SerializeDisplay, DeserializeFromStr,
+)]
+pub enum Grade {
+    /// 不及格
+    Fail,
+    /// 及格
+    Pass,
+    /// 中
+    Medium,
+    /// 良
+    Good,
+    /// 优
+    Excellent,
+}
+
+impl Grade {
+    /// 全部等级，按等级从低到高排列
+    pub const ALL: [Grade; 5] = [
+        Grade::Fail,
+        Grade::Pass,
+        Grade::Medium,
+        Grade::Good,
+        Grade::Excellent,
+    ];
+
+    /// 等级在教务网中的名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            Grade::Fail => "不及格",
+            Grade::Pass => "及格",
+            Grade::Medium => "中",
+            Grade::Good => "良",
+            Grade::Excellent => "优",
+        }
+    }
+
+    /// 等级对应的绩点
+    ///
+    /// 按等级分数段的中间分数（优95、良85、中75、及格65、不及格0）在百分制成绩与绩点对照表中换算，
+    /// 即优4.0、良3.7、中2.7、及格1.5、不及格0，见[`ScoreValue::grade_point`]
+    pub fn grade_point(&self) -> f64 {
+        numeric_grade_point(self.equivalent_score())
+    }
+
+    /// 换算绩点时等级对应的百分制分数，取等级分数段的中间分数
+    fn equivalent_score(&self) -> f64 {
+        match self {
+            Grade::Fail => 0.0,
+            Grade::Pass => 65.0,
+            Grade::Medium => 75.0,
+            Grade::Good => 85.0,
+            Grade::Excellent => 95.0,
+        }
+    }
+
+    /// 将百分制成绩换算为等级，90分及以上为优、80~89分为良、70~79分为中、60~69分为及格、60分以下为不及格
+    pub fn from_numeric(score: f64) -> Self {
+        match score {
+            s if s >= 90.0 => Grade::Excellent,
+            s if s >= 80.0 => Grade::Good,
+            s if s >= 70.0 => Grade::Medium,
+            s if s >= 60.0 => Grade::Pass,
+            _ => Grade::Fail,
+        }
+    }
+}
+
+impl Display for Grade {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Grade {
+    type Err = ApiError<MyCQUError>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grade::ALL
+            .into_iter()
+            .find(|grade| grade.name() == s.trim())
+            .ok_or_else(|| ApiError::ModelParse {
+                msg: "Grade parse error".to_string(),
+                raw_response: s.to_string(),
+            })
+    }
+}
+
+/// 缓考、缺考等不给出成绩的特殊状态
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, SerializeDisplay, DeserializeFromStr)]
+pub enum ScoreStatus {
+    /// 缓考
+    Deferred,
+    /// 缺考
+    Absent,
+    /// 作弊
+    Cheating,
+    /// 免修
+    Exempt,
+}
+
+impl ScoreStatus {
+    /// 全部特殊状态
+    pub const ALL: [ScoreStatus; 4] = [
+        ScoreStatus::Deferred,
+        ScoreStatus::Absent,
+        ScoreStatus::Cheating,
+        ScoreStatus::Exempt,
+    ];
+
+    /// 状态在教务网中的名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            ScoreStatus::Deferred => "缓考",
+            ScoreStatus::Absent => "缺考",
+            ScoreStatus::Cheating => "作弊",
+            ScoreStatus::Exempt => "免修",
+        }
+    }
+}
+
+impl Display for ScoreStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for ScoreStatus {
+    type Err = ApiError<MyCQUError>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ScoreStatus::ALL
+            .into_iter()
+            .find(|status| status.name() == s.trim())
+            .ok_or_else(|| ApiError::ModelParse {
+                msg: "ScoreStatus parse error".to_string(),
+                raw_response: s.to_string(),
+            })
+    }
+}
+
+/// 类型化的成绩，可通过[`Score::value`]由[`Score::score`]解析得到
+///
+/// 同种成绩之间可以比较大小，不同种成绩之间不可比较
+///
+/// # Examples
+/// ```rust
+/// # use rsmycqu::mycqu::score::{Grade, ScoreStatus, ScoreValue};
+/// assert_eq!("92".parse::<ScoreValue>().unwrap(), ScoreValue::Numeric(92.0));
+/// assert_eq!("优".parse::<ScoreValue>().unwrap(), ScoreValue::Grade(Grade::Excellent));
+/// assert_eq!("通过".parse::<ScoreValue>().unwrap(), ScoreValue::PassFail(true));
+/// assert_eq!("缓考".parse::<ScoreValue>().unwrap(), ScoreValue::Status(ScoreStatus::Deferred));
+///
+/// assert!(ScoreValue::Numeric(92.0) > ScoreValue::Numeric(85.0));
+/// assert!(ScoreValue::Grade(Grade::Good) < ScoreValue::Grade(Grade::Excellent));
+/// assert_eq!(ScoreValue::Numeric(92.0).partial_cmp(&ScoreValue::Grade(Grade::Good)), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, SerializeDisplay, DeserializeFromStr)]
+pub enum ScoreValue {
+    /// 百分制成绩
+    Numeric(f64),
+    /// 五级制成绩
+    Grade(Grade),
+    /// 二级制成绩，`true`为通过
+    PassFail(bool),
+    /// 不给出成绩的特殊状态
+    Status(ScoreStatus),
+}
+
+impl ScoreValue {
+    /// 百分制成绩的分数，其余成绩为`None`
+    pub fn as_numeric(&self) -> Option<f64> {
+        match self {
+            ScoreValue::Numeric(score) => Some(*score),
+            _ => None,
+        }
+    }
+
+    /// 成绩对应的五级制等级，百分制成绩按[`Grade::from_numeric`]换算，二级制成绩与特殊状态为`None`
+    pub fn to_grade(&self) -> Option<Grade> {
+        match self {
+            ScoreValue::Numeric(score) => Some(Grade::from_numeric(*score)),
+            ScoreValue::Grade(grade) => Some(*grade),
+            _ => None,
+        }
+    }
+
+    /// 按重庆大学教务处发布的本科生学业成绩管理规定中的成绩与绩点对照表将成绩换算为绩点
+    ///
+    /// 百分制成绩按90分及以上4.0、85~89分3.7、82~84分3.3、78~81分3.0、75~77分2.7、72~74分2.3、
+    /// 68~71分2.0、64~67分1.5、60~63分1.0、60分以下0换算；五级制成绩见[`Grade::grade_point`]。
+    /// 缺考与作弊按0绩点计入；二级制成绩、缓考与免修不计入绩点，返回`None`
+    pub fn grade_point(&self) -> Option<f64> {
+        match self {
+            ScoreValue::Numeric(score) => Some(numeric_grade_point(*score)),
+            ScoreValue::Grade(grade) => Some(grade.grade_point()),
+            ScoreValue::Status(ScoreStatus::Absent | ScoreStatus::Cheating) => Some(0.0),
+            ScoreValue::PassFail(_)
+            | ScoreValue::Status(ScoreStatus::Deferred | ScoreStatus::Exempt) => None,
+        }
+    }
+
+    /// 是否通过该课程，缓考等尚无结果的状态为`None`
+    pub fn is_passed(&self) -> Option<bool> {
+        match self {
+            ScoreValue::Numeric(score) => Some(*score >= 60.0),
+            ScoreValue::Grade(grade) => Some(*grade != Grade::Fail),
+            ScoreValue::PassFail(passed) => Some(*passed),
+            ScoreValue::Status(ScoreStatus::Exempt) => Some(true),
+            ScoreValue::Status(ScoreStatus::Absent | ScoreStatus::Cheating) => Some(false),
+            ScoreValue::Status(ScoreStatus::Deferred) => None,
+        }
+    }
+}
+
+impl PartialOrd for ScoreValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (ScoreValue::Numeric(a), ScoreValue::Numeric(b)) => a.partial_cmp(b),
+            (ScoreValue::Grade(a), ScoreValue::Grade(b)) => Some(a.cmp(b)),
+            (ScoreValue::PassFail(a), ScoreValue::PassFail(b)) => Some(a.cmp(b)),
+            (a, b) => (a == b).then_some(Ordering::Equal),
+        }
+    }
+}
+
+impl Display for ScoreValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScoreValue::Numeric(score) => write!(f, "{score}"),
+            ScoreValue::Grade(grade) => write!(f, "{grade}"),
+            ScoreValue::PassFail(true) => f.write_str("通过"),
+            ScoreValue::PassFail(false) => f.write_str("不通过"),
+            ScoreValue::Status(status) => write!(f, "{status}"),
+        }
+    }
+}
+
+impl FromStr for ScoreValue {
+    type Err = ApiError<MyCQUError>;
+
+    /// 解析教务网给出的成绩字符串，`合格`、`不合格`被视为二级制成绩
+    ///
+    /// 无法识别的成绩会抛出[`ApiError::ModelParse`]异常
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s.trim();
+        if let Some(score) = trimmed
+            .parse::<f64>()
+            .ok()
+            .filter(|score| score.is_finite() && *score >= 0.0)
+        {
+            return Ok(ScoreValue::Numeric(score));
+        }
+
+        match trimmed {
+            "通过" | "合格" => Ok(ScoreValue::PassFail(true)),
+            "不通过" | "不合格" => Ok(ScoreValue::PassFail(false)),
+            _ => trimmed
+                .parse()
+                .map(ScoreValue::Grade)
+                .or_else(|_| trimmed.parse().map(ScoreValue::Status))
+                .map_err(|_| ApiError::ModelParse {
+                    msg: "ScoreValue parse error".to_string(),
+                    raw_response: s.to_string(),
+                }),
+        }
+    }
+}
+
+impl Score {
+    /// 解析后的成绩，尚未出分或成绩无法识别时为`None`
+    pub fn value(&self) -> Option<ScoreValue> {
+        self.score.as_deref().and_then(|score| score.parse().ok())
+    }
+}
@@ -0,0 +1,456 @@
+//! 轮询成绩并报告成绩变化
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    ffi::OsString,
+    fmt::Debug,
+    future::Future,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    pin::Pin,
+    task::{Context, Poll},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use futures_core::Stream;
+use serde::{Deserialize, Serialize};
+use tokio::time::Instant;
+
+use crate::{
+    errors::{
+        ApiError,
+        mycqu::{MyCQUError, MyCQUResult},
+    },
+    mycqu::{
+        course::{CQUSession, Course},
+        score::Score,
+    },
+    session::{Client, Session},
+};
+
+/// 默认的轮询间隔
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// 某一时刻的全部成绩
+///
+/// 序列化时使用独立于教务网接口格式的快照格式
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "StoredSnapshot", into = "StoredSnapshot")]
+pub struct ScoreSnapshot {
+    /// 获取成绩时的Unix时间戳，单位为秒
+    pub taken_at: u64,
+    /// 全部成绩
+    pub scores: Vec<Score>,
+}
+
+impl ScoreSnapshot {
+    /// 以当前时间创建快照
+    pub fn new(scores: Vec<Score>) -> Self {
+        ScoreSnapshot {
+            taken_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_secs())
+                .unwrap_or_default(),
+            scores,
+        }
+    }
+
+    /// 由JSON反序列化快照
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    /// 将快照序列化为格式化的JSON
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("score snapshot should always be serializable")
+    }
+
+    /// 与上一次的快照`previous`相比的成绩变化
+    ///
+    /// 成绩以学期与课程代码识别，没有课程代码时依次使用课程名称、成绩ID。
+    /// 三者均缺失的成绩无法与上一次的成绩对应，只有与上一次完全相同时才不报告变化
+    pub fn diff(&self, previous: &ScoreSnapshot) -> Vec<ScoreEvent> {
+        let previous_scores: HashMap<_, _> = previous
+            .scores
+            .iter()
+            .filter_map(|score| Some((score_key(score)?, score)))
+            .collect();
+        let current_keys: HashSet<_> = self.scores.iter().filter_map(score_key).collect();
+
+        let changed = self.scores.iter().filter_map(|score| {
+            let previous_score = match score_key(score) {
+                Some(key) => previous_scores.get(&key).copied(),
+                None if previous.scores.contains(score) => return None,
+                None => None,
+            };
+            match previous_score {
+                None | Some(Score { score: None, .. }) if score.score.is_some() => {
+                    Some(ScoreEvent::Published {
+                        score: score.clone(),
+                    })
+                }
+                Some(previous) if previous.score.is_some() && previous.score != score.score => {
+                    Some(ScoreEvent::Corrected {
+                        previous: Box::new(previous.clone()),
+                        current: score.clone(),
+                    })
+                }
+                _ => None,
+            }
+        });
+        let removed = previous
+            .scores
+            .iter()
+            .filter(|score| match score_key(score) {
+                Some(key) => !current_keys.contains(&key),
+                None => !self.scores.contains(score),
+            })
+            .map(|score| ScoreEvent::Removed {
+                score: score.clone(),
+            });
+
+        changed.chain(removed).collect()
+    }
+}
+
+/// 识别成绩所属课程的方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum ScoreIdentity<'a> {
+    /// 课程代码或课程名称，见[`Score::course_key`]
+    Course(&'a str),
+    /// 成绩ID
+    Id(&'a str),
+}
+
+/// 识别同一成绩的键，由学期与课程组成，无法识别课程时为`None`
+fn score_key(score: &Score) -> Option<(u16, bool, ScoreIdentity<'_>)> {
+    let identity = score
+        .course_key()
+        .map(ScoreIdentity::Course)
+        .or(score.id.as_deref().map(ScoreIdentity::Id))?;
+    Some((score.session.year, score.session.is_autumn, identity))
+}
+
+/// [`ScoreSnapshot`]的序列化格式
+///
+/// [`Score`]与[`Course`]的序列化格式跟随教务网接口，不适合作为持久化的快照格式，故在此单独定义
+#[derive(Serialize, Deserialize)]
+struct StoredSnapshot {
+    taken_at: u64,
+    scores: Vec<StoredScore>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoredScore {
+    id: Option<String>,
+    session: CQUSession,
+    name: Option<String>,
+    code: Option<String>,
+    course_num: Option<String>,
+    dept: Option<String>,
+    credit: Option<f64>,
+    instructor: Option<String>,
+    course_session: Option<CQUSession>,
+    score: Option<String>,
+    study_nature: String,
+    course_nature: String,
+}
+
+impl From<ScoreSnapshot> for StoredSnapshot {
+    fn from(snapshot: ScoreSnapshot) -> Self {
+        StoredSnapshot {
+            taken_at: snapshot.taken_at,
+            scores: snapshot
+                .scores
+                .into_iter()
+                .map(|score| StoredScore {
+                    id: score.id,
+                    session: score.session,
+                    name: score.course.name,
+                    code: score.course.code,
+                    course_num: score.course.course_num,
+                    dept: score.course.dept,
+                    credit: score.course.credit,
+                    instructor: score.course.instructor,
+                    course_session: score.course.session,
+                    score: score.score,
+                    study_nature: score.study_nature,
+                    course_nature: score.course_nature,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<StoredSnapshot> for ScoreSnapshot {
+    fn from(snapshot: StoredSnapshot) -> Self {
+        ScoreSnapshot {
+            taken_at: snapshot.taken_at,
+            scores: snapshot
+                .scores
+                .into_iter()
+                .map(|score| Score {
+                    id: score.id,
+                    session: score.session,
+                    course: Course {
+                        name: score.name,
+                        code: score.code,
+                        course_num: score.course_num,
+                        dept: score.dept,
+                        credit: score.credit,
+                        instructor: score.instructor,
+                        session: score.course_session,
+                    },
+                    score: score.score,
+                    study_nature: score.study_nature,
+                    course_nature: score.course_nature,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// 成绩变化
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScoreEvent {
+    /// 新成绩发布，包括新出现的课程与此前尚未出分的课程
+    Published {
+        /// 发布的成绩
+        score: Score,
+    },
+    /// 已发布的成绩被更正
+    Corrected {
+        /// 更正前的成绩
+        previous: Box<Score>,
+        /// 更正后的成绩
+        current: Score,
+    },
+    /// 课程从成绩中被移除
+    Removed {
+        /// 被移除前的成绩
+        score: Score,
+    },
+}
+
+impl ScoreEvent {
+    /// 事件涉及的成绩，成绩被更正时为更正后的成绩
+    pub fn score(&self) -> &Score {
+        match self {
+            ScoreEvent::Published { score } | ScoreEvent::Removed { score } => score,
+            ScoreEvent::Corrected { current, .. } => current,
+        }
+    }
+}
+
+/// 定期通过[`Score::fetch_self`]获取成绩，并与上一次获取的成绩比较以报告成绩变化
+///
+/// 第一次获取成绩时只记录快照而不报告变化，可通过[`ScoreWatcher::set_snapshot`]或[`ScoreWatcher::set_snapshot_path`]
+/// 提供上一次运行时保存的快照，使程序重启期间发布的成绩也能被报告
+///
+/// 会话的访问权限失效时可通过[自动重新认证](crate::session::reauth)恢复，请求失败时返回错误并在下一个轮询周期重试
+///
+/// # Examples
+/// ```rust, no_run
+/// # use std::time::Duration;
+/// # use rsmycqu::mycqu::score::{ScoreEvent, ScoreWatcher};
+/// # use rsmycqu::session::{Client, Session};
+/// # async fn watch(client: Client, session: Session) {
+/// let mut watcher = ScoreWatcher::new(client, session)
+///     .set_interval(Duration::from_secs(5 * 60))
+///     .set_snapshot_path("scores.json");
+/// loop {
+///     match watcher.next_event().await {
+///         Ok(ScoreEvent::Published { score }) => println!("{:?}: {:?}", score.course.name, score.score),
+///         Ok(event) => println!("{event:?}"),
+///         Err(err) => eprintln!("{err}"),
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ScoreWatcher {
+    client: Client,
+    session: Session,
+    is_minor: bool,
+    interval: Duration,
+    snapshot: Option<ScoreSnapshot>,
+    snapshot_path: Option<PathBuf>,
+    /// 已获取但尚未返回的成绩变化
+    pending: VecDeque<ScoreEvent>,
+    /// 下一次获取成绩的时间，为`None`时立即获取
+    next_poll: Option<Instant>,
+}
+
+impl ScoreWatcher {
+    /// 使用具有教务网权限的会话([`Session`])创建监听器，默认每10分钟获取一次主修成绩
+    pub fn new(client: Client, session: Session) -> Self {
+        ScoreWatcher {
+            client,
+            session,
+            is_minor: false,
+            interval: DEFAULT_INTERVAL,
+            snapshot: None,
+            snapshot_path: None,
+            pending: VecDeque::new(),
+            next_poll: None,
+        }
+    }
+
+    /// 设置两次获取成绩的间隔
+    pub fn set_interval(self, interval: Duration) -> Self {
+        ScoreWatcher { interval, ..self }
+    }
+
+    /// 设置是否监听辅修成绩
+    pub fn set_is_minor(self, is_minor: bool) -> Self {
+        ScoreWatcher { is_minor, ..self }
+    }
+
+    /// 设置上一次获取的成绩快照，之后获取的成绩将与该快照比较
+    pub fn set_snapshot(self, snapshot: ScoreSnapshot) -> Self {
+        ScoreWatcher {
+            snapshot: Some(snapshot),
+            ..self
+        }
+    }
+
+    /// 设置保存快照的文件，每次获取成绩后以JSON格式写入最新的快照
+    ///
+    /// 未通过[`ScoreWatcher::set_snapshot`]设置快照时，第一次获取成绩前会从该文件读取快照
+    pub fn set_snapshot_path(self, path: impl Into<PathBuf>) -> Self {
+        ScoreWatcher {
+            snapshot_path: Some(path.into()),
+            ..self
+        }
+    }
+
+    /// 最近一次获取的成绩快照
+    pub fn snapshot(&self) -> Option<&ScoreSnapshot> {
+        self.snapshot.as_ref()
+    }
+
+    /// 立即获取一次成绩，返回与上一次快照相比的成绩变化
+    pub async fn poll(&mut self) -> MyCQUResult<Vec<ScoreEvent>> {
+        self.load_snapshot().await?;
+
+        let scores = Score::fetch_self(&self.client, &self.session, self.is_minor).await?;
+        let current = ScoreSnapshot::new(scores);
+        let events = self
+            .snapshot
+            .as_ref()
+            .map(|previous| current.diff(previous))
+            .unwrap_or_default();
+        // 先保存快照，保存失败时下一次获取成绩仍会报告本次的成绩变化
+        self.save_snapshot(&current).await?;
+        self.snapshot = Some(current);
+
+        Ok(events)
+    }
+
+    /// 等待下一个成绩变化，期间按设置的间隔获取成绩
+    ///
+    /// 获取成绩失败时返回错误，再次调用将在下一个轮询周期重试
+    pub async fn next_event(&mut self) -> MyCQUResult<ScoreEvent> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Ok(event);
+            }
+            if let Some(next_poll) = self.next_poll {
+                tokio::time::sleep_until(next_poll).await;
+            }
+            self.next_poll = Some(Instant::now() + self.interval);
+            let events = self.poll().await?;
+            self.pending.extend(events);
+        }
+    }
+
+    /// 转为成绩变化的[`Stream`]，见[`ScoreWatcher::next_event`]
+    ///
+    /// 该[`Stream`]不会结束，获取成绩失败时产生错误后继续轮询
+    pub fn into_stream(self) -> ScoreEventStream {
+        ScoreEventStream {
+            next: next_event(self),
+        }
+    }
+
+    async fn load_snapshot(&mut self) -> MyCQUResult<()> {
+        let (None, Some(path)) = (&self.snapshot, &self.snapshot_path) else {
+            return Ok(());
+        };
+
+        let json = match tokio::fs::read_to_string(path).await {
+            Ok(json) => json,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(snapshot_error("读取成绩快照失败", err)),
+        };
+        self.snapshot = Some(
+            ScoreSnapshot::from_json(&json)
+                .map_err(|err| snapshot_error("解析成绩快照失败", err))?,
+        );
+        Ok(())
+    }
+
+    /// 先写入同目录下的临时文件再替换快照文件，避免写入中断时留下不完整的快照
+    async fn save_snapshot(&self, snapshot: &ScoreSnapshot) -> MyCQUResult<()> {
+        let Some(path) = &self.snapshot_path else {
+            return Ok(());
+        };
+
+        let temp_path = temp_snapshot_path(path);
+        tokio::fs::write(&temp_path, snapshot.to_json())
+            .await
+            .map_err(|err| snapshot_error("保存成绩快照失败", err))?;
+        if let Err(err) = tokio::fs::rename(&temp_path, path).await {
+            let _ = tokio::fs::remove_file(&temp_path).await;
+            return Err(snapshot_error("保存成绩快照失败", err));
+        }
+        Ok(())
+    }
+}
+
+/// 保存快照时使用的临时文件，位于快照文件所在目录以保证替换是原子的
+fn temp_snapshot_path(path: &Path) -> PathBuf {
+    let mut file_name = path.file_name().map(OsString::from).unwrap_or_default();
+    file_name.push(".tmp");
+    path.with_file_name(file_name)
+}
+
+fn snapshot_error(
+    message: &str,
+    err: impl std::error::Error + Send + Sync + 'static,
+) -> ApiError<MyCQUError> {
+    ApiError::Whatever {
+        message: message.to_string(),
+        source: Some(Box::new(err)),
+    }
+}
+
+type NextEvent = Pin<Box<dyn Future<Output = (ScoreWatcher, MyCQUResult<ScoreEvent>)> + Send>>;
+
+fn next_event(mut watcher: ScoreWatcher) -> NextEvent {
+    Box::pin(async move {
+        let result = watcher.next_event().await;
+        (watcher, result)
+    })
+}
+
+/// 成绩变化的[`Stream`]，由[`ScoreWatcher::into_stream`]创建
+pub struct ScoreEventStream {
+    next: NextEvent,
+}
+
+impl Debug for ScoreEventStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ScoreEventStream").finish_non_exhaustive()
+    }
+}
+
+impl Stream for ScoreEventStream {
+    type Item = MyCQUResult<ScoreEvent>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let (watcher, result) = std::task::ready!(self.next.as_mut().poll(cx));
+        self.next = next_event(watcher);
+        Poll::Ready(Some(result))
+    }
+}
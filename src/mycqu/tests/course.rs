@@ -7,7 +7,9 @@ use crate::{
     session::Session,
     utils::{
         models::Period,
-        test_fixture::{LoginData, access_mycqu_session, login_data, shared_client},
+        test_fixture::{
+            LoginData, access_mycqu_session, login_data, replay_session, shared_client,
+        },
     },
 };
 
@@ -314,3 +316,56 @@ async fn test_fetch_enroll_timetable(
     .await
     .unwrap();
 }
+
+#[rstest]
+#[tokio::test]
+async fn test_replay_course(
+    shared_client: &'static crate::session::Client,
+    example_course: Course,
+) {
+    let session = replay_session(include_str!("cassettes/course.json"));
+
+    let sessions = CQUSession::fetch_all(shared_client, &session)
+        .await
+        .unwrap();
+    assert_eq!(
+        sessions,
+        vec![
+            CQUSession {
+                id: Some(1046),
+                year: 2024,
+                is_autumn: false
+            },
+            CQUSession {
+                id: Some(1045),
+                year: 2023,
+                is_autumn: true
+            },
+        ]
+    );
+
+    let session_infos = CQUSessionInfo::fetch_all(shared_client, &session)
+        .await
+        .unwrap();
+    assert_eq!(session_infos.len(), 2);
+    let curr = CQUSessionInfo::fetch_curr(shared_client, &session)
+        .await
+        .unwrap();
+    assert!(curr.active);
+    assert_eq!(
+        CQUSessionInfo::fetch_detail(shared_client, &session, 1046)
+            .await
+            .unwrap(),
+        curr
+    );
+
+    let timetables = CourseTimetable::fetch_curr(shared_client, &session, "20200001", 1046)
+        .await
+        .unwrap();
+    assert_eq!(timetables.len(), 1);
+    assert_eq!(timetables[0].course, example_course);
+    let enroll_timetables = CourseTimetable::fetch_enroll(shared_client, &session, "20200001")
+        .await
+        .unwrap();
+    assert_eq!(enroll_timetables, timetables);
+}
@@ -4,7 +4,7 @@ use crate::{
     errors::ApiError,
     mycqu::enroll::{EnrollCourseInfo, EnrollCourseItem},
     session::Session,
-    utils::test_fixture::{access_mycqu_session, shared_client},
+    utils::test_fixture::{access_mycqu_session, replay_session, shared_client},
 };
 
 #[rstest]
@@ -50,3 +50,23 @@ async fn test_get_enroll_course_item(
         .await
         .unwrap();
 }
+
+#[rstest]
+#[tokio::test]
+async fn test_replay_enroll(shared_client: &'static crate::session::Client) {
+    let session = replay_session(include_str!("cassettes/enroll.json"));
+
+    let infos = EnrollCourseInfo::fetch_all(shared_client, &session, true)
+        .await
+        .unwrap();
+    let courses = &infos["主修专业课"];
+    assert_eq!(courses.len(), 1);
+    assert_eq!(courses[0].id, "10000004872");
+
+    let items = EnrollCourseItem::fetch_all(shared_client, &session, &courses[0].id, true)
+        .await
+        .unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].capacity, Some(120));
+    assert_eq!(items[0].timetables.len(), 2);
+}
@@ -1,21 +1,22 @@
 use rstest::*;
 
 use crate::{
-    errors::ApiError,
+    errors::{ApiError, mycqu::MyCQUError},
     mycqu::{
         User, access_mycqu,
         exam::Exam,
-        score::{GPARanking, Score},
+        score::{GPARanking, GPARankingReport, Grade, Score, ScoreComponentKind, ScoreValue},
     },
     session::Session,
     utils::test_fixture::{
-        LoginData, access_mycqu_session, login_data, login_session, shared_client,
+        LoginData, access_mycqu_session, login_data, login_session, replay_session, shared_client,
     },
 };
 
 mod course;
 mod enroll;
 mod exam;
+mod score;
 
 #[rstest]
 #[ignore]
@@ -91,11 +92,11 @@ async fn test_get_gpa_ranking(
 ) {
     {
         let session = Session::new();
-        let res = GPARanking::fetch_self(shared_client, &session).await;
+        let res = GPARanking::fetch_self(shared_client, &session, false).await;
         assert!(res.is_err());
         assert!(matches!(res.unwrap_err(), ApiError::NotAccess));
     }
-    GPARanking::fetch_self(shared_client, &access_mycqu_session.await)
+    GPARankingReport::fetch_self(shared_client, &access_mycqu_session.await)
         .await
         .unwrap();
 }
@@ -122,3 +123,112 @@ async fn test_get_exam(
     .await
     .unwrap();
 }
+
+#[rstest]
+#[tokio::test]
+async fn test_replay_user(shared_client: &'static crate::session::Client) {
+    let session = replay_session(include_str!("cassettes/user.json"));
+    let user = User::fetch_self(shared_client, &session).await.unwrap();
+    assert_eq!(user.name, "张三");
+    assert_eq!(user.code, "20200001");
+    assert_eq!(user.role, "student");
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_replay_score_and_gpa_ranking(shared_client: &'static crate::session::Client) {
+    let session = replay_session(include_str!("cassettes/score.json"));
+
+    let scores = Score::fetch_self(shared_client, &session, false)
+        .await
+        .unwrap();
+    assert_eq!(scores.len(), 2);
+    assert_eq!(scores[0].score.as_deref(), Some("92"));
+    assert_eq!(scores[1].score.as_deref(), Some("优"));
+    assert_eq!(scores[0].value(), Some(ScoreValue::Numeric(92.0)));
+    assert_eq!(scores[1].value(), Some(ScoreValue::Grade(Grade::Excellent)));
+
+    let detail = scores[0]
+        .fetch_detail(shared_client, &session)
+        .await
+        .unwrap();
+    assert_eq!(detail.score_id, scores[0].id);
+    assert_eq!(detail.components.len(), 3);
+    let usual = detail.component(ScoreComponentKind::Usual).unwrap();
+    assert_eq!(usual.weight, Some(30.0));
+    assert_eq!(usual.value(), Some(ScoreValue::Numeric(95.0)));
+    assert!(detail.component(ScoreComponentKind::Lab).is_none());
+
+    let mut without_id = scores[1].clone();
+    without_id.id = None;
+    assert!(matches!(
+        without_id.fetch_detail(shared_client, &session).await,
+        Err(ApiError::Inner {
+            source: MyCQUError::MissingScoreId
+        })
+    ));
+
+    let ranking = GPARanking::fetch_self(shared_client, &session, false)
+        .await
+        .unwrap();
+    assert_eq!(ranking.gpa, 3.62);
+    assert_eq!(ranking.major_ranking, Some(12));
+    assert_eq!(ranking.grade_ranking, None);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_replay_gpa_ranking_report(shared_client: &'static crate::session::Client) {
+    let session = replay_session(include_str!("cassettes/score.json"));
+    let report = GPARankingReport::fetch_self(shared_client, &session)
+        .await
+        .unwrap();
+    assert_eq!(report.major.gpa, 3.62);
+    assert_eq!(report.minor.as_ref().unwrap().major_ranking, Some(5));
+    assert_eq!(report.minor_gpa(), Some(3.4));
+    assert_eq!(report.minor_weighted_avg(), Some(85.2));
+
+    // 未修读辅修专业时辅修绩点排名为空
+    let mut cassette: serde_json::Value =
+        serde_json::from_str(include_str!("cassettes/score.json")).unwrap();
+    let interactions = cassette["interactions"].as_array_mut().unwrap();
+    let minor = interactions
+        .iter_mut()
+        .find(|interaction| {
+            interaction["request"]["url"]
+                .as_str()
+                .unwrap()
+                .ends_with("isMinorBoo=true")
+        })
+        .unwrap();
+    minor["response"]["body"] = r#"{"status": "success", "msg": null, "data": null}"#.into();
+    let session = replay_session(&cassette.to_string());
+    let report = GPARankingReport::fetch_self(shared_client, &session)
+        .await
+        .unwrap();
+    assert_eq!(report.minor, None);
+    assert_eq!(report.minor_gpa(), None);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_replay_exam(shared_client: &'static crate::session::Client) {
+    let session = replay_session(include_str!("cassettes/exam.json"));
+    let exams = Exam::fetch_all(shared_client, &session, "20200001")
+        .await
+        .unwrap();
+    assert_eq!(exams.len(), 1);
+    assert_eq!(exams[0].room, "D1337");
+    assert_eq!(exams[0].chief_invigilator.len(), 1);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_replay_mismatch(shared_client: &'static crate::session::Client) {
+    let session = replay_session(include_str!("cassettes/user.json"));
+    User::fetch_self(shared_client, &session).await.unwrap();
+
+    // 录像中的记录只能被使用一次
+    let res = User::fetch_self(shared_client, &session).await;
+    assert!(matches!(res.unwrap_err(), ApiError::Session { .. }));
+}
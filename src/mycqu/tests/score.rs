@@ -0,0 +1,512 @@
+use std::{future::poll_fn, pin::Pin, str::FromStr, time::Duration};
+
+use futures_core::Stream;
+use rstest::*;
+use serde_json::{Value, json};
+
+use crate::{
+    mycqu::{
+        course::{CQUSession, Course},
+        score::{
+            GPACalculator, Grade, Score, ScoreComponentKind, ScoreDetail, ScoreEvent,
+            ScoreSnapshot, ScoreStatus, ScoreValue, ScoreWatcher, grade_point,
+        },
+    },
+    session::Client,
+    utils::test_fixture::{replay_session, shared_client},
+};
+
+fn score(session: &str, code: &str, credit: f64, value: Option<&str>, study_nature: &str) -> Score {
+    Score {
+        id: None,
+        session: session.parse().unwrap(),
+        course: Course {
+            name: Some(code.to_string()),
+            code: Some(code.to_string()),
+            course_num: None,
+            dept: None,
+            credit: Some(credit),
+            instructor: None,
+            session: None,
+        },
+        score: value.map(ToString::to_string),
+        study_nature: study_nature.to_string(),
+        course_nature: "必修".to_string(),
+    }
+}
+
+#[rstest]
+#[case("100", Some(4.0))]
+#[case("90", Some(4.0))]
+#[case("89.5", Some(3.7))]
+#[case("84", Some(3.3))]
+#[case("78", Some(3.0))]
+#[case("75", Some(2.7))]
+#[case("72", Some(2.3))]
+#[case("68", Some(2.0))]
+#[case("64", Some(1.5))]
+#[case("60", Some(1.0))]
+#[case("59", Some(0.0))]
+#[case("优", Some(4.0))]
+#[case("良", Some(3.7))]
+#[case("中", Some(2.7))]
+#[case("及格", Some(1.5))]
+#[case("不及格", Some(0.0))]
+#[case("通过", None)]
+#[case("缓考", None)]
+#[case("免修", None)]
+#[case("缺考", Some(0.0))]
+#[case("作弊", Some(0.0))]
+fn test_grade_point(#[case] score: &str, #[case] expected: Option<f64>) {
+    assert_eq!(grade_point(score), expected);
+}
+
+#[rstest]
+fn test_gpa_calculator() {
+    let scores = vec![
+        score("2023秋", "MATH10821", 6.0, Some("92"), "初修"),
+        score("2023秋", "PESS10001", 1.0, Some("优"), "初修"),
+        score("2023秋", "PHYS10001", 4.0, Some("55"), "初修"),
+        score("2023秋", "MARX10001", 2.0, Some("通过"), "初修"),
+        score("2024春", "PHYS10001", 4.0, Some("80"), "重修"),
+        score("2024春", "CST10001", 3.0, None, "初修"),
+    ];
+    let calculator = GPACalculator::new(scores);
+    let report = calculator.calculate();
+
+    // 重修课程只计入绩点最高的一次，通过与未出分的课程不计入
+    assert_eq!(report.overall.courses, 3);
+    assert_eq!(report.overall.credits, 11.0);
+    let expected = (4.0 * 6.0 + 4.0 * 1.0 + 3.0 * 4.0) / 11.0;
+    assert!((report.overall.gpa.unwrap() - expected).abs() < 1e-9);
+    let expected_avg = (92.0 * 6.0 + 80.0 * 4.0) / 10.0;
+    assert!((report.overall.weighted_avg.unwrap() - expected_avg).abs() < 1e-9);
+
+    // 各学期计入该学期实际修读的成绩
+    assert_eq!(
+        report
+            .sessions
+            .iter()
+            .map(|session| session.session.to_string())
+            .collect::<Vec<_>>(),
+        vec!["2023秋", "2024春"]
+    );
+    let autumn = &report.sessions[0].summary;
+    assert!((autumn.gpa.unwrap() - (4.0 * 6.0 + 4.0 * 1.0) / 11.0).abs() < 1e-9);
+    assert_eq!(report.sessions[1].summary.gpa, Some(3.0));
+
+    // 预估未出分课程的绩点
+    let what_if = calculator.clone().set_score("CST10001", "良").calculate();
+    assert_eq!(what_if.overall.courses, 4);
+    assert_eq!(
+        what_if.sessions[1].summary.gpa,
+        Some((3.0 * 4.0 + 3.7 * 3.0) / 7.0)
+    );
+
+    // 仅计算部分课程
+    let math = calculator
+        .filter(|score| score.course.code.as_deref() == Some("MATH10821"))
+        .calculate();
+    assert_eq!(math.overall.gpa, Some(4.0));
+    assert_eq!(math.sessions.len(), 1);
+}
+
+#[rstest]
+fn test_gpa_calculator_retake() {
+    let report = GPACalculator::new(vec![
+        score("2024春", "PHYS10001", 4.0, Some("70"), "重修"),
+        score("2023秋", "PHYS10001", 4.0, Some("55"), "初修"),
+        score("2024秋", "PHYS10001", 4.0, Some("65"), "重修"),
+        score("2023秋", "PESS10001", 1.0, Some("优"), "初修"),
+        score("2024春", "PESS10001", 1.0, Some("良"), "初修"),
+    ])
+    .calculate();
+
+    // 重修成绩取绩点最高的一次，未标记重修的多次修读各自计入
+    assert_eq!(report.overall.courses, 3);
+    assert_eq!(report.overall.credits, 6.0);
+    let expected = (2.0 * 4.0 + 4.0 + 3.7) / 6.0;
+    assert!((report.overall.gpa.unwrap() - expected).abs() < 1e-9);
+}
+
+#[rstest]
+fn test_gpa_calculator_weighted_avg() {
+    let report = GPACalculator::new(vec![
+        score("2023秋", "MATH10821", 2.0, Some(" 89.5 "), "初修"),
+        score("2023秋", "PESS10001", 1.0, Some("优"), "初修"),
+        score("2023秋", "MARX10001", 2.0, Some("缓考"), "初修"),
+    ])
+    .calculate();
+
+    // 只有百分制成绩计入加权平均分，五级制成绩只计入绩点
+    assert_eq!(report.overall.weighted_avg, Some(89.5));
+    assert_eq!(report.overall.credits, 3.0);
+}
+
+#[rstest]
+fn test_gpa_calculator_absent() {
+    let report = GPACalculator::new(vec![
+        score("2023秋", "MATH10821", 2.0, Some("92"), "初修"),
+        score("2023秋", "PHYS10001", 2.0, Some("缺考"), "初修"),
+        score("2023秋", "CST10001", 2.0, Some("缓考"), "初修"),
+    ])
+    .calculate();
+
+    // 缺考按0绩点计入，缓考不计入
+    assert_eq!(report.overall.courses, 2);
+    assert_eq!(report.overall.credits, 4.0);
+    assert_eq!(report.overall.gpa, Some(2.0));
+    assert_eq!(report.overall.weighted_avg, Some(92.0));
+
+    // 缺考的绩点不高于参加考试但不及格
+    let failed = GPACalculator::new(vec![
+        score("2023秋", "MATH10821", 2.0, Some("92"), "初修"),
+        score("2023秋", "PHYS10001", 2.0, Some("55"), "初修"),
+    ])
+    .calculate();
+    assert!(report.overall.gpa <= failed.overall.gpa);
+}
+
+#[rstest]
+fn test_gpa_calculator_empty() {
+    let report = GPACalculator::new(Vec::new())
+        .add_score(score("2023秋", "MARX10001", 2.0, Some("通过"), "初修"))
+        .calculate();
+    assert_eq!(report.overall.gpa, None);
+    assert_eq!(report.overall.weighted_avg, None);
+    assert_eq!(
+        report.sessions[0].session,
+        CQUSession::from_str("2023秋").unwrap()
+    );
+}
+
+#[rstest]
+#[case("92", ScoreValue::Numeric(92.0))]
+#[case(" 89.5 ", ScoreValue::Numeric(89.5))]
+#[case("优", ScoreValue::Grade(Grade::Excellent))]
+#[case("不及格", ScoreValue::Grade(Grade::Fail))]
+#[case("通过", ScoreValue::PassFail(true))]
+#[case("不合格", ScoreValue::PassFail(false))]
+#[case("缓考", ScoreValue::Status(ScoreStatus::Deferred))]
+#[case("作弊", ScoreValue::Status(ScoreStatus::Cheating))]
+fn test_score_value_parse(#[case] raw: &str, #[case] expected: ScoreValue) {
+    let value: ScoreValue = raw.parse().unwrap();
+    assert_eq!(value, expected);
+    assert_eq!(value.to_string().parse::<ScoreValue>().unwrap(), expected);
+}
+
+#[rstest]
+#[case("")]
+#[case("NaN")]
+#[case("-1")]
+#[case("A+")]
+fn test_score_value_parse_error(#[case] raw: &str) {
+    assert!(raw.parse::<ScoreValue>().is_err());
+}
+
+#[rstest]
+fn test_score_value_helpers() {
+    assert!(ScoreValue::Numeric(59.5) < ScoreValue::Numeric(60.0));
+    assert!(ScoreValue::Grade(Grade::Medium) > ScoreValue::Grade(Grade::Pass));
+    assert!(ScoreValue::PassFail(true) > ScoreValue::PassFail(false));
+    assert_eq!(
+        ScoreValue::Numeric(92.0).partial_cmp(&ScoreValue::PassFail(true)),
+        None
+    );
+
+    assert_eq!(ScoreValue::Numeric(85.0).to_grade(), Some(Grade::Good));
+    assert_eq!(ScoreValue::PassFail(true).to_grade(), None);
+    assert_eq!(ScoreValue::Numeric(59.0).is_passed(), Some(false));
+    assert_eq!(ScoreValue::Grade(Grade::Pass).is_passed(), Some(true));
+    assert_eq!(
+        ScoreValue::Status(ScoreStatus::Absent).is_passed(),
+        Some(false)
+    );
+    assert_eq!(ScoreValue::Status(ScoreStatus::Deferred).is_passed(), None);
+    assert_eq!(ScoreValue::Status(ScoreStatus::Exempt).grade_point(), None);
+
+    // 五级制成绩的绩点与对照表中该等级分数段内某一分数的绩点一致
+    for grade in Grade::ALL {
+        assert!((0..=100).any(|score| {
+            let value = ScoreValue::Numeric(score as f64);
+            value.to_grade() == Some(grade) && value.grade_point() == Some(grade.grade_point())
+        }));
+    }
+
+    let mut scored = score("2023秋", "MATH10821", 6.0, Some("缺考"), "初修");
+    assert_eq!(
+        scored.value(),
+        Some(ScoreValue::Status(ScoreStatus::Absent))
+    );
+    scored.score = None;
+    assert_eq!(scored.value(), None);
+
+    assert_eq!(
+        serde_json::to_string(&ScoreValue::Grade(Grade::Good)).unwrap(),
+        "\"良\""
+    );
+    assert_eq!(
+        serde_json::from_str::<ScoreValue>("\"92\"").unwrap(),
+        ScoreValue::Numeric(92.0)
+    );
+}
+
+fn score_item(code: &str, score: Option<&str>) -> Value {
+    json!({
+        "sessionName": "2023秋",
+        "courseName": code,
+        "courseCode": code,
+        "courseCredit": "2.0",
+        "score": score,
+        "effectiveScoreShow": score,
+        "studyNature": "初修",
+        "courseNature": "必修"
+    })
+}
+
+/// 依次返回`polls`中各组成绩的录像
+fn scores_cassette(polls: Vec<Vec<Value>>) -> String {
+    let interactions: Vec<Value> = polls
+        .into_iter()
+        .map(|items| {
+            let body = json!({
+                "status": "success",
+                "msg": null,
+                "data": {"2023秋": {"stuScoreHomePgVoS": items}}
+            });
+            json!({
+                "request": {
+                    "method": "GET",
+                    "url": "https://my.cqu.edu.cn/api/sam/score/student/score?isMinorBoo=false",
+                    "body": null
+                },
+                "response": {
+                    "status": 200,
+                    "headers": [["content-type", "application/json;charset=UTF-8"]],
+                    "body": body.to_string()
+                }
+            })
+        })
+        .collect();
+    json!({ "interactions": interactions }).to_string()
+}
+
+fn event_summary(event: &ScoreEvent) -> (&'static str, String, Option<String>) {
+    let kind = match event {
+        ScoreEvent::Published { .. } => "published",
+        ScoreEvent::Corrected { .. } => "corrected",
+        ScoreEvent::Removed { .. } => "removed",
+    };
+    let score = event.score();
+    (
+        kind,
+        score.course.code.clone().unwrap(),
+        score.score.clone(),
+    )
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_score_watcher_events(shared_client: &'static Client) {
+    let session = replay_session(&scores_cassette(vec![
+        vec![
+            score_item("MATH10821", Some("92")),
+            score_item("PESS10001", None),
+            score_item("PHYS10001", Some("80")),
+        ],
+        vec![
+            score_item("MATH10821", Some("93")),
+            score_item("PESS10001", Some("优")),
+            score_item("PHYS10001", Some("80")),
+            score_item("CST10001", None),
+        ],
+        vec![
+            score_item("MATH10821", Some("93")),
+            score_item("PESS10001", Some("优")),
+            score_item("CST10001", Some("通过")),
+        ],
+    ]));
+    let mut watcher = ScoreWatcher::new(shared_client.clone(), session);
+
+    // 第一次获取成绩只记录快照
+    assert!(watcher.poll().await.unwrap().is_empty());
+    assert_eq!(watcher.snapshot().unwrap().scores.len(), 3);
+
+    let events = watcher.poll().await.unwrap();
+    assert_eq!(
+        events.iter().map(event_summary).collect::<Vec<_>>(),
+        vec![
+            ("corrected", "MATH10821".to_string(), Some("93".to_string())),
+            ("published", "PESS10001".to_string(), Some("优".to_string())),
+        ]
+    );
+    let ScoreEvent::Corrected { previous, .. } = &events[0] else {
+        unreachable!()
+    };
+    assert_eq!(previous.score.as_deref(), Some("92"));
+
+    assert_eq!(
+        watcher
+            .poll()
+            .await
+            .unwrap()
+            .iter()
+            .map(event_summary)
+            .collect::<Vec<_>>(),
+        vec![
+            (
+                "published",
+                "CST10001".to_string(),
+                Some("通过".to_string())
+            ),
+            ("removed", "PHYS10001".to_string(), Some("80".to_string())),
+        ]
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_score_watcher_stream_and_snapshot(shared_client: &'static Client) {
+    let path = std::env::temp_dir().join(format!(
+        "rsmycqu-score-snapshot-{}.json",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+
+    let session = replay_session(&scores_cassette(vec![
+        vec![score_item("MATH10821", None)],
+        vec![score_item("MATH10821", None)],
+        vec![score_item("MATH10821", Some("92"))],
+    ]));
+    let mut stream = ScoreWatcher::new(shared_client.clone(), session)
+        .set_interval(Duration::from_millis(10))
+        .set_snapshot_path(&path)
+        .into_stream();
+    let event = poll_fn(|cx| Pin::new(&mut stream).poll_next(cx))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        event_summary(&event),
+        ("published", "MATH10821".to_string(), Some("92".to_string()))
+    );
+
+    // 保存的快照可以被恢复，重启后发生的成绩变化同样会被报告
+    let snapshot = ScoreSnapshot::from_json(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(snapshot.scores, vec![event.score().clone()]);
+    assert_eq!(
+        ScoreSnapshot::from_json(&snapshot.to_json()).unwrap(),
+        snapshot
+    );
+
+    let session = replay_session(&scores_cassette(vec![vec![score_item(
+        "MATH10821",
+        Some("95"),
+    )]]));
+    let mut watcher = ScoreWatcher::new(shared_client.clone(), session).set_snapshot_path(&path);
+    let events = watcher.poll().await.unwrap();
+    // 快照经临时文件替换写入，不留下临时文件
+    assert!(!path.with_extension("json.tmp").exists());
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(
+        events.iter().map(event_summary).collect::<Vec<_>>(),
+        vec![("corrected", "MATH10821".to_string(), Some("95".to_string()))]
+    );
+}
+
+#[rstest]
+fn test_score_detail_lenient_parse() {
+    let detail: ScoreDetail = serde_json::from_value(json!({
+        "effectiveScoreShow": "92",
+        "scoreDetails": [
+            {"itemName": "平时成绩", "proportion": "30", "score": 95},
+            {"itemName": "期中成绩", "proportion": 20, "score": 88.5},
+            {"itemName": "期末成绩", "proportion": 50, "score": "优"},
+            {"itemName": "实验成绩", "score": null}
+        ]
+    }))
+    .unwrap();
+
+    // 数字成绩与缺失的ID不会导致解析失败
+    assert_eq!(detail.score_id, None);
+    assert_eq!(
+        detail
+            .components
+            .iter()
+            .map(|component| component.score.as_deref())
+            .collect::<Vec<_>>(),
+        vec![Some("95"), Some("88.5"), Some("优"), None]
+    );
+    assert_eq!(
+        detail.component(ScoreComponentKind::Usual).unwrap().value(),
+        Some(ScoreValue::Numeric(95.0))
+    );
+}
+
+#[rstest]
+fn test_score_snapshot_round_trip() {
+    let mut published = score("2023秋", "MATH10821", 6.0, Some("92"), "初修");
+    published.id = Some("1001".to_string());
+    published.course.course_num = Some("001".to_string());
+    published.course.dept = Some("数学与统计学院".to_string());
+    published.course.instructor = Some("张三".to_string());
+    published.course.session = Some("2023秋".parse().unwrap());
+    let mut pending = score("2024春", "CST10001", 3.0, None, "初修");
+    pending.course.name = None;
+    pending.course.credit = None;
+
+    let snapshot = ScoreSnapshot {
+        taken_at: 1700000000,
+        scores: vec![published, pending],
+    };
+    assert_eq!(
+        ScoreSnapshot::from_json(&snapshot.to_json()).unwrap(),
+        snapshot
+    );
+}
+
+#[rstest]
+fn test_score_snapshot_diff_without_course_key() {
+    let unnamed = |id: Option<&str>, value: &str| {
+        let mut score = score("2023秋", "", 1.0, Some(value), "初修");
+        score.id = id.map(ToString::to_string);
+        score.course.code = None;
+        score.course.name = None;
+        score
+    };
+    let previous = ScoreSnapshot {
+        taken_at: 0,
+        scores: vec![unnamed(None, "优"), unnamed(Some("1"), "80")],
+    };
+    let current = ScoreSnapshot {
+        taken_at: 1,
+        scores: vec![
+            unnamed(None, "优"),
+            unnamed(None, "良"),
+            unnamed(Some("1"), "85"),
+            unnamed(Some("2"), "90"),
+        ],
+    };
+
+    // 无法识别课程的成绩不会相互覆盖，有成绩ID时以成绩ID识别
+    let events: Vec<_> = current
+        .diff(&previous)
+        .into_iter()
+        .map(|event| {
+            let kind = match event {
+                ScoreEvent::Published { .. } => "published",
+                ScoreEvent::Corrected { .. } => "corrected",
+                ScoreEvent::Removed { .. } => "removed",
+            };
+            (kind, event.score().score.clone().unwrap())
+        })
+        .collect();
+    assert_eq!(
+        events,
+        vec![
+            ("published", "良".to_string()),
+            ("corrected", "85".to_string()),
+            ("published", "90".to_string()),
+        ]
+    );
+}
@@ -1,12 +1,11 @@
-use std::collections::HashMap;
-
+use serde::Deserialize;
 use serde_json::Value;
 
 use crate::{
     errors::mycqu::{MyCQUError, MyCQUResult},
-    session::{Client, Session},
+    session::{Client, Session, access_info::MyCQUAccessInfo},
     utils::{
-        consts::{MYCQU_AUTHORIZE_URL, MYCQU_TOKEN_INDEX_URL, MYCQU_TOKEN_URL},
+        consts::{MYCQU_AUTHORIZE_PATH, MYCQU_TOKEN_INDEX_PATH, MYCQU_TOKEN_PATH},
         get_response_header,
     },
 };
@@ -20,32 +19,82 @@ fn find_code(location: &(impl AsRef<str> + ?Sized)) -> MyCQUResult<&str> {
         .as_str())
 }
 
+const CLIENT_ID: &str = "enroll-prod";
+const CLIENT_SECRET: &str = "app-a-1234";
+
+/// 令牌接口的响应
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: Option<u64>,
+    refresh_token: Option<String>,
+}
+
+impl From<TokenResponse> for MyCQUAccessInfo {
+    fn from(token: TokenResponse) -> Self {
+        MyCQUAccessInfo::issued_now(token.access_token, token.expires_in, token.refresh_token)
+    }
+}
+
+/// 向令牌接口提交表单并解析返回的令牌
+async fn request_token(
+    client: &Client,
+    session: &Session,
+    form: &[(&str, &str)],
+) -> MyCQUResult<MyCQUAccessInfo> {
+    let res = session
+        .execute(
+            client
+                .post(client.endpoints().mycqu_url(MYCQU_TOKEN_PATH))
+                .form(form),
+        )
+        .await?;
+    if !res.status().is_success() {
+        return Err(MyCQUError::AccessError.into());
+    }
+
+    serde_json::from_value::<TokenResponse>(res.json::<Value>().await?)
+        .map(Into::into)
+        .map_err(|_| MyCQUError::AccessError.into())
+}
+
 pub(in crate::mycqu) async fn get_oauth_token(
     client: &Client,
     session: &mut Session,
-) -> MyCQUResult<String> {
-    let res = session.execute(client.get(MYCQU_AUTHORIZE_URL)).await?;
+) -> MyCQUResult<MyCQUAccessInfo> {
+    let token_index_url = client.endpoints().mycqu_url(MYCQU_TOKEN_INDEX_PATH);
+    let authorize_url = format!(
+        "{}?client_id={CLIENT_ID}&response_type=code&scope=all&state=&redirect_uri={}",
+        client.endpoints().mycqu_url(MYCQU_AUTHORIZE_PATH),
+        token_index_url
+    );
+    let res = session.execute(client.get(authorize_url)).await?;
     let code = find_code(get_response_header(&res, "Location").ok_or(MyCQUError::AccessError)?)?;
     let token_data = [
-        ("client_id", "enroll-prod"),
-        ("client_secret", "app-a-1234"),
+        ("client_id", CLIENT_ID),
+        ("client_secret", CLIENT_SECRET),
         ("code", code),
-        ("redirect_uri", MYCQU_TOKEN_INDEX_URL),
+        ("redirect_uri", &token_index_url),
         ("grant_type", "authorization_code"),
     ];
 
-    let access_res = session
-        .execute(client.post(MYCQU_TOKEN_URL).form(&token_data))
-        .await?
-        .json::<HashMap<String, Value>>()
-        .await?;
+    request_token(client, session, &token_data).await
+}
 
-    access_res
-        .get("access_token")
-        .ok_or(MyCQUError::AccessError)?
-        .as_str()
-        .map(ToString::to_string)
-        .ok_or(MyCQUError::AccessError.into())
+/// 使用`refresh_token`换取新的令牌
+pub(in crate::mycqu) async fn refresh_oauth_token(
+    client: &Client,
+    session: &Session,
+    refresh_token: &str,
+) -> MyCQUResult<MyCQUAccessInfo> {
+    let token_data = [
+        ("client_id", CLIENT_ID),
+        ("client_secret", CLIENT_SECRET),
+        ("refresh_token", refresh_token),
+        ("grant_type", "refresh_token"),
+    ];
+
+    request_token(client, session, &token_data).await
 }
 
 #[cfg(test)]
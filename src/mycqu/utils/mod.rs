@@ -5,7 +5,7 @@ use snafu::ensure;
 use crate::{
     errors,
     errors::{ApiError, mycqu::MyCQUResult},
-    session::{Client, Session},
+    session::{Client, Session, access_info::Service, reauth::reauthenticate},
 };
 
 pub(super) mod access;
@@ -17,24 +17,58 @@ pub(super) async fn mycqu_request_handler<T>(
     f: T,
 ) -> MyCQUResult<Response>
 where
-    T: FnOnce(&Client) -> RequestBuilder,
+    T: Fn(&Client) -> RequestBuilder,
 {
-    let access_info = if let Some(access_info) = session.access_infos.mycqu_access_info.as_ref() {
-        access_info
-    } else {
-        return Err(ApiError::NotAccess);
-    };
-
-    let res = session
-        .execute(f(client).bearer_auth(access_info.auth_header.as_str()))
-        .await?;
-
-    if res.status() == StatusCode::UNAUTHORIZED {
-        return Err(ApiError::NotAccess);
+    let auth_header = mycqu_auth_header(session).ok_or(ApiError::NotAccess)?;
+
+    match mycqu_request(session, f(client), &auth_header).await {
+        // 访问权限失效时，若启用了自动重新认证则重新获取权限并写回会话，随后使用新的令牌重新构建请求发送一次
+        Err(ApiError::NotAccess) => {
+            let auth_header = match mycqu_auth_header(session) {
+                // 其他请求已经重新认证并写回了新的令牌
+                Some(current) if current != auth_header => current,
+                _ => {
+                    let mut fresh = reauthenticate(client, session, Service::MyCQU)
+                        .await
+                        .ok_or(ApiError::NotAccess)?;
+                    let access_info = fresh.access_infos.get_mut().mycqu_access_info.take();
+                    let auth_header = access_info
+                        .as_ref()
+                        .map(|access_info| access_info.auth_header.clone())
+                        .ok_or(ApiError::NotAccess)?;
+                    session.access_infos.write().mycqu_access_info = access_info;
+                    auth_header
+                }
+            };
+            mycqu_request(session, f(client), &auth_header).await
+        }
+        res => res,
     }
+}
+
+async fn mycqu_request(
+    session: &Session,
+    builder: RequestBuilder,
+    auth_header: &str,
+) -> MyCQUResult<Response> {
+    let res = session.execute(builder.bearer_auth(auth_header)).await?;
+    ensure!(
+        res.status() != StatusCode::UNAUTHORIZED,
+        errors::NotAccessSnafu
+    );
     Ok(res)
 }
 
+#[inline]
+fn mycqu_auth_header(session: &Session) -> Option<String> {
+    session
+        .access_infos
+        .read()
+        .mycqu_access_info
+        .as_ref()
+        .map(|access_info| access_info.auth_header.clone())
+}
+
 /// 检查响应json的status字段是否为error，如果是则返回错误
 pub(super) fn check_website_response(res: &Map<String, Value>) -> MyCQUResult<()> {
     ensure!(
@@ -1,13 +1,150 @@
 //! 各模块支持服务所需的登陆信息
 
+use std::sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+#[cfg(feature = "mycqu")]
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
 use serde::{Deserialize, Serialize};
 
+use crate::session::Session;
+
 /// [`mycqu`](crate::mycqu)所需的登陆信息
 #[cfg(feature = "mycqu")]
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct MyCQUAccessInfo {
     /// 该字符串应当添加进后续访问请求头的`Authorization`项中
     pub(crate) auth_header: String,
+    /// 令牌签发时的Unix时间戳（秒）
+    pub(crate) issued_at: Option<u64>,
+    /// 令牌自签发起的有效期（秒）
+    pub(crate) expires_in: Option<u64>,
+    /// 用于刷新令牌的`refresh_token`
+    pub(crate) refresh_token: Option<String>,
+}
+
+#[cfg(feature = "mycqu")]
+impl MyCQUAccessInfo {
+    /// 由令牌构建登陆信息，签发时间、有效期与`refresh_token`均未知
+    ///
+    /// 可用于将在其他机器上获取的令牌设置到会话中
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use std::time::{Duration, SystemTime};
+    /// # use rsmycqu::session::{Session, access_info::{AccessInfos, MyCQUAccessInfo, Service}};
+    /// let access_info = MyCQUAccessInfo::new("token")
+    ///     .set_expiry(SystemTime::now(), Duration::from_secs(3600))
+    ///     .set_refresh_token("refresh_token");
+    /// let session = Session::new().set_access_infos(AccessInfos::new().set_mycqu(access_info));
+    /// assert!(session.has_access(Service::MyCQU));
+    /// ```
+    pub fn new(auth_header: impl Into<String>) -> Self {
+        MyCQUAccessInfo {
+            auth_header: auth_header.into(),
+            issued_at: None,
+            expires_in: None,
+            refresh_token: None,
+        }
+    }
+
+    /// 由令牌接口的响应构建登陆信息，签发时间记为当前时间
+    pub(crate) fn issued_now(
+        auth_header: String,
+        expires_in: Option<u64>,
+        refresh_token: Option<String>,
+    ) -> Self {
+        MyCQUAccessInfo {
+            auth_header,
+            issued_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .ok()
+                .map(|duration| duration.as_secs()),
+            expires_in,
+            refresh_token,
+        }
+    }
+
+    /// 设置令牌的签发时间与有效期
+    pub fn set_expiry(self, issued_at: SystemTime, expires_in: Duration) -> Self {
+        MyCQUAccessInfo {
+            issued_at: issued_at
+                .duration_since(UNIX_EPOCH)
+                .ok()
+                .map(|duration| duration.as_secs()),
+            expires_in: Some(expires_in.as_secs()),
+            ..self
+        }
+    }
+
+    /// 设置用于刷新令牌的`refresh_token`
+    pub fn set_refresh_token(self, refresh_token: impl Into<String>) -> Self {
+        MyCQUAccessInfo {
+            refresh_token: Some(refresh_token.into()),
+            ..self
+        }
+    }
+
+    /// 访问教务网接口所用的令牌，即请求头`Authorization: Bearer <token>`中的`token`
+    pub fn auth_header(&self) -> &str {
+        &self.auth_header
+    }
+
+    /// 用于刷新令牌的`refresh_token`
+    pub fn refresh_token(&self) -> Option<&str> {
+        self.refresh_token.as_deref()
+    }
+
+    /// 令牌自签发起的有效期
+    pub fn expires_in(&self) -> Option<Duration> {
+        self.expires_in.map(Duration::from_secs)
+    }
+
+    /// 令牌签发时间
+    pub fn issued_at(&self) -> Option<SystemTime> {
+        self.issued_at
+            .map(|issued_at| UNIX_EPOCH + Duration::from_secs(issued_at))
+    }
+
+    /// 令牌过期时间，令牌接口未返回有效期时为`None`
+    pub fn expires_at(&self) -> Option<SystemTime> {
+        self.issued_at()
+            .zip(self.expires_in)
+            .map(|(issued_at, expires_in)| issued_at + Duration::from_secs(expires_in))
+    }
+
+    /// 令牌的剩余有效时间，已过期时为[`Duration::ZERO`]，过期时间未知时为`None`
+    pub fn remaining(&self) -> Option<Duration> {
+        self.expires_at().map(|expires_at| {
+            expires_at
+                .duration_since(SystemTime::now())
+                .unwrap_or(Duration::ZERO)
+        })
+    }
+
+    /// 令牌是否已过期，过期时间未知时视为未过期
+    pub fn is_expired(&self) -> bool {
+        self.remaining() == Some(Duration::ZERO)
+    }
+
+    /// 是否可以通过`refresh_token`刷新令牌
+    pub fn can_refresh(&self) -> bool {
+        self.refresh_token.is_some()
+    }
+}
+
+#[cfg(feature = "mycqu")]
+impl Session {
+    /// 教务网令牌的剩余有效时间
+    ///
+    /// 未获取教务网访问权限或令牌过期时间未知时为`None`，已过期时为[`Duration::ZERO`]，
+    /// 剩余时间不足时可通过[`refresh_mycqu`](crate::mycqu::refresh_mycqu)刷新令牌
+    pub fn mycqu_access_remaining(&self) -> Option<Duration> {
+        self.access_infos
+            .read()
+            .mycqu_access_info
+            .as_ref()
+            .and_then(MyCQUAccessInfo::remaining)
+    }
 }
 
 /// [`card`](crate::card)所需的登陆信息
@@ -18,6 +155,37 @@ pub struct CardAccessInfo {
     pub(crate) synjones_auth: Option<String>,
 }
 
+#[cfg(feature = "card")]
+impl CardAccessInfo {
+    /// 构建尚未获取水电费查询账号信息的登陆信息
+    ///
+    /// 校园卡网站的访问权限保存在会话的Cookie中，因此仅在会话Cookie有效时该登陆信息才可用
+    pub fn new() -> Self {
+        CardAccessInfo {
+            synjones_auth: None,
+        }
+    }
+
+    /// 设置用于宿舍水电费查询的账号信息
+    pub fn set_synjones_auth(self, synjones_auth: impl Into<String>) -> Self {
+        CardAccessInfo {
+            synjones_auth: Some(synjones_auth.into()),
+        }
+    }
+
+    /// 用于宿舍水电费查询的账号信息
+    pub fn synjones_auth(&self) -> Option<&str> {
+        self.synjones_auth.as_deref()
+    }
+}
+
+#[cfg(feature = "card")]
+impl Default for CardAccessInfo {
+    fn default() -> Self {
+        CardAccessInfo::new()
+    }
+}
+
 /// 包含各模块支持服务所需的登陆信息
 #[derive(Debug, Clone, Eq, PartialEq, Default, Serialize, Deserialize)]
 pub struct AccessInfos {
@@ -26,3 +194,147 @@ pub struct AccessInfos {
     #[cfg(feature = "card")]
     pub(crate) card_access_info: Option<CardAccessInfo>,
 }
+
+/// [`Session`]中保存访问信息的容器
+///
+/// 以`&Session`调用的接口在自动重新认证后需要将新的访问信息写回会话，故使用内部可变性；
+/// 克隆时复制其中的访问信息，会话的克隆之间互不影响
+#[derive(Default)]
+pub(crate) struct AccessInfosCell(RwLock<AccessInfos>);
+
+impl AccessInfosCell {
+    pub(crate) fn read(&self) -> RwLockReadGuard<'_, AccessInfos> {
+        self.0.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    #[cfg(any(feature = "mycqu", feature = "card"))]
+    pub(crate) fn write(&self) -> RwLockWriteGuard<'_, AccessInfos> {
+        self.0.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub(crate) fn get_mut(&mut self) -> &mut AccessInfos {
+        self.0.get_mut().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl From<AccessInfos> for AccessInfosCell {
+    fn from(infos: AccessInfos) -> Self {
+        AccessInfosCell(RwLock::new(infos))
+    }
+}
+
+impl Clone for AccessInfosCell {
+    fn clone(&self) -> Self {
+        self.read().clone().into()
+    }
+}
+
+impl std::fmt::Debug for AccessInfosCell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.read().fmt(f)
+    }
+}
+
+impl AccessInfos {
+    /// 构建不包含任何服务登陆信息的[`AccessInfos`]
+    pub fn new() -> Self {
+        AccessInfos::default()
+    }
+
+    /// 设置教务网的登陆信息
+    #[cfg(feature = "mycqu")]
+    pub fn set_mycqu(mut self, access_info: MyCQUAccessInfo) -> Self {
+        // 各服务的字段受feature控制，无法使用结构体更新语法
+        self.mycqu_access_info = Some(access_info);
+        self
+    }
+
+    /// 教务网的登陆信息
+    #[cfg(feature = "mycqu")]
+    pub fn mycqu(&self) -> Option<&MyCQUAccessInfo> {
+        self.mycqu_access_info.as_ref()
+    }
+
+    /// 设置校园卡的登陆信息
+    #[cfg(feature = "card")]
+    pub fn set_card(mut self, access_info: CardAccessInfo) -> Self {
+        // 各服务的字段受feature控制，无法使用结构体更新语法
+        self.card_access_info = Some(access_info);
+        self
+    }
+
+    /// 校园卡的登陆信息
+    #[cfg(feature = "card")]
+    pub fn card(&self) -> Option<&CardAccessInfo> {
+        self.card_access_info.as_ref()
+    }
+
+    /// 是否包含`service`的登陆信息
+    pub fn has_access(&self, service: Service) -> bool {
+        match service {
+            #[cfg(feature = "mycqu")]
+            Service::MyCQU => self.mycqu_access_info.is_some(),
+            #[cfg(feature = "card")]
+            Service::Card => self.card_access_info.is_some(),
+        }
+    }
+
+    /// 包含登陆信息的全部服务
+    pub fn services(&self) -> Vec<Service> {
+        Service::ALL
+            .iter()
+            .copied()
+            .filter(|service| self.has_access(*service))
+            .collect()
+    }
+
+    /// 移除`service`的登陆信息
+    pub fn clear(&mut self, service: Service) {
+        match service {
+            #[cfg(feature = "mycqu")]
+            Service::MyCQU => self.mycqu_access_info = None,
+            #[cfg(feature = "card")]
+            Service::Card => self.card_access_info = None,
+        }
+    }
+}
+
+/// 需要单独获取访问权限的服务
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum Service {
+    /// 教务网，见[`mycqu`](crate::mycqu)
+    #[cfg(feature = "mycqu")]
+    MyCQU,
+    /// 校园卡，见[`card`](crate::card)
+    #[cfg(feature = "card")]
+    Card,
+}
+
+impl Service {
+    /// 当前启用的feature支持的全部服务
+    pub const ALL: &'static [Service] = &[
+        #[cfg(feature = "mycqu")]
+        Service::MyCQU,
+        #[cfg(feature = "card")]
+        Service::Card,
+    ];
+}
+
+impl Session {
+    /// 会话是否拥有`service`的访问信息
+    ///
+    /// 该方法只检查会话中是否保存了访问信息，不会检查访问信息是否已经失效
+    pub fn has_access(&self, service: Service) -> bool {
+        self.access_infos.read().has_access(service)
+    }
+
+    /// 会话拥有访问信息的全部服务
+    pub fn services(&self) -> Vec<Service> {
+        self.access_infos.read().services()
+    }
+
+    /// 移除会话中`service`的访问信息，不影响该会话的克隆
+    pub fn clear_access(&mut self, service: Service) {
+        self.access_infos.get_mut().clear(service)
+    }
+}
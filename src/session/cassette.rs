@@ -0,0 +1,229 @@
+//! 请求录制与回放
+//!
+//! 为[`Session`]设置[`Cassette`]后，会话发出的全部请求都会经过录制或回放：
+//!
+//! - [`CassetteMode::Record`]：请求照常发出，请求与响应在脱敏（移除密码、票据、令牌、Cookie值等）后追加到录像中
+//! - [`CassetteMode::Replay`]：请求不会发出，而是按顺序使用录像中方法与链接相匹配的记录构造响应
+//!
+//! 录像可通过[`Cassette::to_json`]持久化，从而在无网络的环境中确定性地复现接口调用
+//!
+//! # Examples
+//! ```rust, no_run
+//! # use rsmycqu::session::{Client, Session, cassette::{Cassette, CassetteMode}};
+//! # use rsmycqu::mycqu::User;
+//! # async fn replay() {
+//! let client = Client::default();
+//! let cassette = Cassette::from_json(&std::fs::read_to_string("user.json").unwrap()).unwrap();
+//! let session = Session::new().set_cassette(cassette, CassetteMode::Replay);
+//! let user = User::fetch_self(&client, &session).await;
+//! # }
+//! ```
+
+use std::sync::Mutex;
+
+use http::response::Builder;
+use reqwest::{Request, Response, ResponseBuilderExt, Url};
+use serde::{Deserialize, Serialize};
+use snafu::OptionExt;
+
+use crate::{
+    errors::session::{CassetteMismatchSnafu, SessionError},
+    session::{Session, transport::SharedTransport},
+    utils::redact::{redact_body, redact_headers, redact_url},
+};
+
+/// 录像的工作模式
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum CassetteMode {
+    /// 发出真实请求并录制
+    Record,
+    /// 使用录像中的记录代替真实请求
+    Replay,
+}
+
+/// 录制的请求
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    /// 请求方法
+    pub method: String,
+    /// 脱敏后的请求链接
+    pub url: String,
+    /// 脱敏后的请求体
+    #[serde(default)]
+    pub body: Option<String>,
+}
+
+/// 录制的响应
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    /// 状态码
+    pub status: u16,
+    /// 脱敏后的响应头
+    #[serde(default)]
+    pub headers: Vec<(String, String)>,
+    /// 脱敏后的响应体，非UTF-8内容会被有损转换
+    #[serde(default)]
+    pub body: String,
+}
+
+/// 一次请求与其响应
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Interaction {
+    /// 请求
+    pub request: RecordedRequest,
+    /// 响应
+    pub response: RecordedResponse,
+}
+
+/// 按发出顺序保存的一组请求与响应
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Cassette {
+    /// 全部请求与响应
+    pub interactions: Vec<Interaction>,
+}
+
+impl Cassette {
+    /// 由JSON反序列化录像
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    /// 将录像序列化为格式化的JSON
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("cassette should always be serializable")
+    }
+}
+
+/// 会话持有的录像状态，与会话的所有克隆共享
+#[derive(Debug)]
+pub(crate) struct CassettePlayer {
+    mode: CassetteMode,
+    state: Mutex<PlayerState>,
+}
+
+#[derive(Debug)]
+struct PlayerState {
+    cassette: Cassette,
+    /// 回放时各记录是否已被使用
+    used: Vec<bool>,
+}
+
+impl CassettePlayer {
+    fn new(cassette: Cassette, mode: CassetteMode) -> Self {
+        let used = vec![false; cassette.interactions.len()];
+        CassettePlayer {
+            mode,
+            state: Mutex::new(PlayerState { cassette, used }),
+        }
+    }
+
+    /// 录制或回放一次请求
+    pub(crate) async fn execute(
+        &self,
+        transport: &SharedTransport,
+        client: &reqwest::Client,
+        request: Request,
+    ) -> Result<Response, SessionError> {
+        let recorded_request = RecordedRequest {
+            method: request.method().to_string(),
+            url: redact_url(request.url()),
+            body: request
+                .body()
+                .and_then(|body| body.as_bytes())
+                .map(|bytes| redact_body(&String::from_utf8_lossy(bytes))),
+        };
+
+        match self.mode {
+            CassetteMode::Replay => self.replay(&recorded_request, request.url().clone()),
+            CassetteMode::Record => {
+                let response = transport.0.execute(client, request).await?;
+                let url = response.url().clone();
+                let status = response.status();
+                let headers = response.headers().clone();
+                let body = response.bytes().await?;
+
+                self.state
+                    .lock()
+                    .unwrap()
+                    .cassette
+                    .interactions
+                    .push(Interaction {
+                        request: recorded_request,
+                        response: RecordedResponse {
+                            status: status.as_u16(),
+                            headers: redact_headers(&headers),
+                            body: redact_body(&String::from_utf8_lossy(&body)),
+                        },
+                    });
+
+                let mut builder = Builder::new().status(status).url(url);
+                if let Some(response_headers) = builder.headers_mut() {
+                    *response_headers = headers;
+                }
+                Ok(builder
+                    .body(body)
+                    .expect("recorded response should be valid")
+                    .into())
+            }
+        }
+    }
+
+    fn replay(&self, request: &RecordedRequest, url: Url) -> Result<Response, SessionError> {
+        let mut state = self.state.lock().unwrap();
+        let PlayerState { cassette, used } = &mut *state;
+        let index = cassette
+            .interactions
+            .iter()
+            .zip(used.iter())
+            .position(|(interaction, used)| {
+                !used
+                    && interaction.request.method == request.method
+                    && interaction.request.url == request.url
+            })
+            .context(CassetteMismatchSnafu {
+                method: request.method.clone(),
+                url: request.url.clone(),
+            })?;
+        used[index] = true;
+
+        let recorded = &cassette.interactions[index].response;
+        let mut builder = Builder::new().status(recorded.status).url(url);
+        for (name, value) in &recorded.headers {
+            builder = builder.header(name, value);
+        }
+        Ok(builder
+            .body(recorded.body.clone())
+            .map_err(|_| SessionError::CassetteMismatch {
+                method: request.method.clone(),
+                url: request.url.clone(),
+            })?
+            .into())
+    }
+
+    fn cassette(&self) -> Cassette {
+        self.state.lock().unwrap().cassette.clone()
+    }
+}
+
+impl Session {
+    /// 为会话设置录像，设置后会话及其之后的克隆发出的全部请求都会按`mode`录制或回放
+    pub fn set_cassette(self, cassette: Cassette, mode: CassetteMode) -> Self {
+        Session {
+            cassette: Some(std::sync::Arc::new(CassettePlayer::new(cassette, mode))),
+            ..self
+        }
+    }
+
+    /// 移除会话的录像
+    pub fn clear_cassette(self) -> Self {
+        Session {
+            cassette: None,
+            ..self
+        }
+    }
+
+    /// 获取会话当前的录像，录制模式下包含设置录像后录制的全部请求
+    pub fn cassette(&self) -> Option<Cassette> {
+        self.cassette.as_ref().map(|player| player.cassette())
+    }
+}
@@ -1,18 +1,23 @@
 //! new type of [`reqwest::Client`]
 
-use std::fmt::Debug;
+use std::{fmt::Debug, sync::Arc};
 
 use reqwest::{ClientBuilder, IntoUrl, redirect::Policy};
 
-use crate::errors::session::SessionError;
+use crate::{errors::session::SessionError, session::Endpoints};
 
 /// [`reqwest::Client`]的`new type`
 ///
 /// [`rsmycqu`](crate)的正确运行要求[`reqwest::Client`]关闭自动跳转
 ///
 /// [`Client`]的唯二构造方法`default`, `custom`保证了这一点
+///
+/// [`Client`]同时持有各服务的根地址配置[`Endpoints`]，默认指向重庆大学各服务的真实地址
 #[derive(Clone, Debug)]
-pub struct Client(reqwest::Client);
+pub struct Client {
+    inner: reqwest::Client,
+    endpoints: Arc<Endpoints>,
+}
 
 impl Default for Client {
     /// [`Client`]默认构建
@@ -25,7 +30,10 @@ impl Default for Client {
             .redirect(Policy::none())
             .build()
             .unwrap();
-        Client(req_client)
+        Client {
+            inner: req_client,
+            endpoints: Arc::new(Endpoints::default()),
+        }
     }
 }
 
@@ -46,16 +54,32 @@ impl Client {
             .redirect(Policy::none())
             .build()?;
 
-        Ok(Client(client))
+        Ok(Client {
+            inner: client,
+            endpoints: Arc::new(Endpoints::default()),
+        })
+    }
+
+    /// 获取当前使用的服务根地址配置
+    pub fn endpoints(&self) -> &Endpoints {
+        &self.endpoints
+    }
+
+    /// 设置服务根地址配置
+    pub fn set_endpoints(self, endpoints: Endpoints) -> Self {
+        Client {
+            endpoints: Arc::new(endpoints),
+            ..self
+        }
     }
 }
 
 impl Client {
     pub(crate) fn get<U: IntoUrl>(&self, url: U) -> reqwest::RequestBuilder {
-        self.0.get(url)
+        self.inner.get(url)
     }
 
     pub(crate) fn post<U: IntoUrl>(&self, url: U) -> reqwest::RequestBuilder {
-        self.0.post(url)
+        self.inner.post(url)
     }
 }
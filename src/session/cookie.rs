@@ -0,0 +1,65 @@
+//! 可导出全部Cookie的Cookie存储
+
+use std::sync::RwLock;
+
+use cookie_store::Cookie;
+use reqwest::{Url, cookie::CookieStore, header::HeaderValue};
+
+/// 基于[`cookie_store::CookieStore`]的Cookie存储
+///
+/// 与[`reqwest::cookie::Jar`]行为一致，但额外支持导出和导入全部Cookie，以便[`Session`](super::Session)持久化
+#[derive(Debug, Default)]
+pub(crate) struct CookieJar(RwLock<cookie_store::CookieStore>);
+
+impl CookieJar {
+    /// 通过`Set-Cookie`格式的字符串向存储中添加Cookie
+    pub(crate) fn add_cookie_str(&self, cookie: &str, url: &Url) {
+        let cookies = cookie_store::RawCookie::parse(cookie)
+            .ok()
+            .map(|c| c.into_owned())
+            .into_iter();
+        self.0.write().unwrap().store_response_cookies(cookies, url);
+    }
+
+    /// 导出所有未过期的Cookie
+    pub(crate) fn export(&self) -> Vec<Cookie<'static>> {
+        self.0.read().unwrap().iter_unexpired().cloned().collect()
+    }
+
+    /// 由导出的Cookie重新构建存储，已过期的Cookie会被丢弃
+    pub(crate) fn import(cookies: Vec<Cookie<'static>>) -> Self {
+        let Ok(store) = cookie_store::CookieStore::from_cookies(
+            cookies.into_iter().map(Ok::<_, std::convert::Infallible>),
+            false,
+        );
+        CookieJar(RwLock::new(store))
+    }
+}
+
+impl CookieStore for CookieJar {
+    fn set_cookies(&self, cookie_headers: &mut dyn Iterator<Item = &HeaderValue>, url: &Url) {
+        let cookies = cookie_headers
+            .filter_map(|value| value.to_str().ok())
+            .filter_map(|value| cookie_store::RawCookie::parse(value).ok())
+            .map(|cookie| cookie.into_owned());
+
+        self.0.write().unwrap().store_response_cookies(cookies, url);
+    }
+
+    fn cookies(&self, url: &Url) -> Option<HeaderValue> {
+        let header = self
+            .0
+            .read()
+            .unwrap()
+            .get_request_values(url)
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect::<Vec<_>>()
+            .join("; ");
+
+        if header.is_empty() {
+            return None;
+        }
+
+        HeaderValue::from_str(&header).ok()
+    }
+}
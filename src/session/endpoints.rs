@@ -0,0 +1,99 @@
+//! 各服务的根地址配置
+
+use serde::{Deserialize, Serialize};
+
+/// 各服务的根地址配置，由[`Client`](super::Client)持有并在所有接口中使用
+///
+/// 默认值即为重庆大学各服务的真实地址，可通过修改其中的字段将请求指向本地模拟服务、镜像或反向代理
+///
+/// 所有地址均不应以`/`结尾（结尾的`/`会在拼接时被忽略）
+///
+/// # Examples
+/// ```rust
+/// # use rsmycqu::session::{Client, Endpoints};
+/// let endpoints = Endpoints {
+///     sso: "http://127.0.0.1:8080".to_string(),
+///     ..Endpoints::default()
+/// };
+/// let client = Client::default().set_endpoints(endpoints);
+///
+/// assert_eq!(client.endpoints().sso, "http://127.0.0.1:8080");
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Endpoints {
+    /// 统一身份认证根地址，默认为`https://sso.cqu.edu.cn`
+    pub sso: String,
+    /// 教务网根地址，默认为`https://my.cqu.edu.cn`
+    pub mycqu: String,
+    /// 校园卡网站根地址，默认为`http://card.cqu.edu.cn`
+    pub card: String,
+    /// 校园卡身份认证服务根地址，默认为`http://card.cqu.edu.cn:7280`
+    pub card_ias: String,
+    /// 校园卡水电费服务根地址，默认为`http://card.cqu.edu.cn:8080`
+    pub card_blade: String,
+}
+
+impl Default for Endpoints {
+    fn default() -> Self {
+        Endpoints {
+            sso: "https://sso.cqu.edu.cn".to_string(),
+            mycqu: "https://my.cqu.edu.cn".to_string(),
+            card: "http://card.cqu.edu.cn".to_string(),
+            card_ias: "http://card.cqu.edu.cn:7280".to_string(),
+            card_blade: "http://card.cqu.edu.cn:8080".to_string(),
+        }
+    }
+}
+
+#[cfg(feature = "sso")]
+#[inline]
+fn join(root: &str, path: &str) -> String {
+    format!("{}{}", root.trim_end_matches('/'), path)
+}
+
+impl Endpoints {
+    /// 以相同的根地址构建所有服务的地址，常用于指向本地模拟服务
+    ///
+    /// ```rust
+    /// # use rsmycqu::session::Endpoints;
+    /// let endpoints = Endpoints::with_root("http://127.0.0.1:8080");
+    /// assert_eq!(endpoints.mycqu, "http://127.0.0.1:8080");
+    /// assert_eq!(endpoints.card_blade, "http://127.0.0.1:8080");
+    /// ```
+    pub fn with_root(root: impl AsRef<str>) -> Self {
+        let root = root.as_ref().trim_end_matches('/').to_string();
+        Endpoints {
+            sso: root.clone(),
+            mycqu: root.clone(),
+            card: root.clone(),
+            card_ias: root.clone(),
+            card_blade: root,
+        }
+    }
+
+    #[cfg(feature = "sso")]
+    pub(crate) fn sso_url(&self, path: &str) -> String {
+        join(&self.sso, path)
+    }
+
+    #[cfg(feature = "mycqu")]
+    pub(crate) fn mycqu_url(&self, path: &str) -> String {
+        join(&self.mycqu, path)
+    }
+
+    #[cfg(feature = "card")]
+    pub(crate) fn card_url(&self, path: &str) -> String {
+        join(&self.card, path)
+    }
+
+    #[cfg(feature = "card")]
+    pub(crate) fn card_ias_url(&self, path: &str) -> String {
+        join(&self.card_ias, path)
+    }
+
+    #[cfg(feature = "card")]
+    pub(crate) fn card_blade_url(&self, path: &str) -> String {
+        join(&self.card_blade, path)
+    }
+}
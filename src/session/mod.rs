@@ -3,16 +3,39 @@
 use std::sync::Arc;
 
 pub use client::Client;
+pub use endpoints::Endpoints;
 pub use reqwest;
-pub(crate) use reqwest::{
-    cookie::{CookieStore, Jar},
-    header,
-};
+pub(crate) use reqwest::{cookie::CookieStore, header};
+use serde::{Deserialize, Serialize};
 
-use crate::{errors::session::SessionError, session::access_info::AccessInfos};
+use crate::{
+    errors::session::SessionError,
+    session::{
+        access_info::{AccessInfos, AccessInfosCell},
+        cassette::CassettePlayer,
+        cookie::CookieJar,
+        observer::Observers,
+        rate_limit::RateLimiter,
+        reauth::ReAuth,
+        retry::RetryPolicy,
+        transport::SharedTransport,
+    },
+};
 
 pub mod access_info;
+pub mod cassette;
 mod client;
+mod cookie;
+mod endpoints;
+pub mod observer;
+pub mod pool;
+pub mod rate_limit;
+pub mod reauth;
+pub mod retry;
+pub mod transport;
+
+#[cfg(test)]
+mod tests;
 
 /// 发起校园信息请求的会话
 ///
@@ -20,11 +43,23 @@ mod client;
 /// [`Session`]的`new`, `custom`方法保证了这一点
 #[derive(Clone, Debug)]
 pub struct Session {
-    cookie_jar: Arc<Jar>,
+    cookie_jar: Arc<CookieJar>,
     /// 是否已经通过[`sso`](crate::sso)登陆
     pub(crate) is_login: bool,
     /// 登陆后获取到的各服务访问信息
-    pub(crate) access_infos: AccessInfos,
+    pub(crate) access_infos: AccessInfosCell,
+    /// 自动重新认证配置
+    pub(crate) reauth: ReAuth,
+    /// 请求失败时的重试策略
+    retry_policy: RetryPolicy,
+    /// 按主机限制请求的限流器，与该会话的所有克隆共享
+    rate_limiter: Option<RateLimiter>,
+    /// 请求观察者
+    observers: Observers,
+    /// 发送请求的传输层
+    transport: SharedTransport,
+    /// 请求录制与回放，与该会话的所有克隆共享
+    cassette: Option<Arc<CassettePlayer>>,
 }
 
 impl Session {
@@ -33,9 +68,15 @@ impl Session {
     /// 该示例存储了验证所需的信息，从而安全的在client中使用
     pub fn new() -> Self {
         Session {
-            cookie_jar: Arc::new(Jar::default()),
+            cookie_jar: Arc::new(CookieJar::default()),
             is_login: false,
-            access_infos: AccessInfos::default(),
+            access_infos: AccessInfosCell::default(),
+            reauth: ReAuth::default(),
+            retry_policy: RetryPolicy::none(),
+            rate_limiter: None,
+            observers: Observers::default(),
+            transport: SharedTransport::default(),
+            cassette: None,
         }
     }
 
@@ -43,7 +84,8 @@ impl Session {
     ///
     /// 这个方法是与外界交互的核心。它会：
     /// 1. 从会话的 `cookie_jar` 中提取相关Cookie，并添加到请求头。
-    /// 2. 发送请求。
+    /// 2. 按[`RateLimiter`](rate_limit::RateLimiter)的限制等待后，通过会话的[`Transport`](transport::Transport)发送请求，
+    ///    失败时按[`RetryPolicy`](retry::RetryPolicy)重试，每次发送后通知[`Observer`](observer::Observer)。
     /// 3. 从响应中提取 `Set-Cookie` 头，并更新 `cookie_jar`。
     ///
     /// # Arguments
@@ -67,8 +109,26 @@ impl Session {
             builder = builder.header(header::COOKIE, cookie_header)
         };
 
-        // 3. 发送请求
-        let response = builder.send().await?;
+        // 3. 通过传输层发送请求，遇到可重试的失败时按重试策略重新发送
+        let (client, request) = builder.build_split();
+        let request = request?;
+
+        #[cfg(feature = "tracing")]
+        let response = {
+            use tracing::Instrument;
+
+            let span = tracing::debug_span!(
+                target: "rsmycqu::session",
+                "request",
+                method = %request.method(),
+                url = %crate::utils::redact::redact_url(request.url()),
+            );
+            self.send_with_retry(&client, request)
+                .instrument(span)
+                .await?
+        };
+        #[cfg(not(feature = "tracing"))]
+        let response = self.send_with_retry(&client, request).await?;
 
         // 4. 从响应中提取 Set-Cookie 并更新 Jar
         let response_url = response.url().clone();
@@ -83,6 +143,56 @@ impl Session {
 
         Ok(response)
     }
+
+    /// 发送请求，遇到可重试的失败时按重试策略重新发送
+    async fn send_with_retry(
+        &self,
+        client: &reqwest::Client,
+        mut request: reqwest::Request,
+    ) -> Result<reqwest::Response, SessionError> {
+        let mut attempt = 1;
+        loop {
+            let retry_request = (attempt < self.retry_policy.max_attempts())
+                .then(|| request.try_clone())
+                .flatten();
+            let method = request.method().clone();
+            let result = self.send(client, request, attempt).await;
+
+            match retry_request.zip(self.retry_policy.retry_after(attempt, &method, &result)) {
+                Some((retry_request, delay)) => {
+                    tokio::time::sleep(delay).await;
+                    request = retry_request;
+                    attempt += 1;
+                }
+                None => break result,
+            }
+        }
+    }
+
+    /// 发送一次请求，设置了录像时由录像录制或回放
+    async fn send(
+        &self,
+        client: &reqwest::Client,
+        request: reqwest::Request,
+        attempt: u32,
+    ) -> Result<reqwest::Response, SessionError> {
+        // 收到响应前请求一直占用限流器的进行中名额
+        let _permit = match (&self.rate_limiter, request.url().host_str()) {
+            (Some(rate_limiter), Some(host)) => Some(rate_limiter.acquire(host).await),
+            _ => None,
+        };
+
+        let pending = self.observers.start(&request, attempt);
+        let result = match &self.cassette {
+            Some(cassette) => cassette.execute(&self.transport, client, request).await,
+            None => self.transport.0.execute(client, request).await,
+        };
+        if let Some(pending) = pending {
+            self.observers.finish(pending, &result);
+        }
+
+        result
+    }
 }
 
 impl Session {
@@ -92,19 +202,73 @@ impl Session {
     }
 
     /// 获取登录信息
-    pub fn access_infos(&self) -> &AccessInfos {
-        &self.access_infos
+    ///
+    /// 返回当前登录信息的副本，自动重新认证可能在之后更新会话中的登录信息，见[`reauth`](crate::session::reauth)
+    pub fn access_infos(&self) -> AccessInfos {
+        self.access_infos.read().clone()
     }
 
     /// 设置登录信息
     pub fn set_access_infos(self, infos: AccessInfos) -> Self {
         Session {
-            access_infos: infos,
+            access_infos: infos.into(),
             ..self
         }
     }
 }
 
+impl Session {
+    /// 生成当前会话的快照，快照包含全部未过期的Cookie、登录状态及各服务访问信息
+    ///
+    /// 快照实现了[`Serialize`]与[`Deserialize`]，可在持久化后通过[`Session::restore`]恢复会话，
+    /// 从而在进程重启后无需重新登录即可直接调用各服务接口
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use rsmycqu::session::Session;
+    /// let session = Session::new();
+    /// let stored = serde_json::to_string(&session.snapshot()).unwrap();
+    ///
+    /// let restored = Session::restore(serde_json::from_str(&stored).unwrap());
+    /// assert_eq!(restored.is_login(), session.is_login());
+    /// ```
+    pub fn snapshot(&self) -> SessionSnapshot {
+        SessionSnapshot {
+            cookies: self.cookie_jar.export(),
+            is_login: self.is_login,
+            access_infos: self.access_infos(),
+        }
+    }
+
+    /// 由[`SessionSnapshot`]恢复会话
+    ///
+    /// 恢复的会话拥有独立的Cookie存储，不会与生成快照的会话共享
+    pub fn restore(snapshot: SessionSnapshot) -> Self {
+        Session {
+            cookie_jar: Arc::new(CookieJar::import(snapshot.cookies)),
+            is_login: snapshot.is_login,
+            access_infos: snapshot.access_infos.into(),
+            reauth: ReAuth::default(),
+            retry_policy: RetryPolicy::none(),
+            rate_limiter: None,
+            observers: Observers::default(),
+            transport: SharedTransport::default(),
+            cassette: None,
+        }
+    }
+}
+
+/// [`Session`]的可序列化快照，由[`Session::snapshot`]生成，通过[`Session::restore`]恢复
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionSnapshot {
+    /// 会话中全部未过期的Cookie
+    cookies: Vec<cookie_store::Cookie<'static>>,
+    /// 是否已经通过[`sso`](crate::sso)登陆
+    is_login: bool,
+    /// 登陆后获取到的各服务访问信息
+    access_infos: AccessInfos,
+}
+
 impl Default for Session {
     fn default() -> Self {
         Session::new()
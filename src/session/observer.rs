@@ -0,0 +1,205 @@
+//! 观察会话发出的请求
+//!
+//! 通过[`Session::add_observer`]添加[`Observer`]后，[`Session::execute`]每次发送请求（包括重试）后都会以[`RequestEvent`]
+//! 报告请求方法、链接、状态码、耗时与重定向目标，便于排查[`ModelParse`](crate::errors::ApiError::ModelParse)等错误对应的请求
+//!
+//! [`rsmycqu`](crate)禁用了自动重定向，统一身份认证等流程中的每一跳重定向都是一次单独的请求，
+//! 按顺序收集[`RequestEvent::redirect`]即可得到完整的重定向链
+//!
+//! 事件中的密码、加密后的`password`表单字段、票据、`Authorization`等请求头与Cookie值均已脱敏
+//!
+//! 启用`tracing` feature后，每次请求还会在`rsmycqu::session`目标下产生`request` span与`DEBUG`级别的事件
+//!
+//! # Examples
+//! ```rust
+//! # use rsmycqu::session::{Session, observer::RequestEvent};
+//! let session = Session::new().add_observer(|event: &RequestEvent| {
+//!     println!(
+//!         "{} {} -> {:?} ({:?})",
+//!         event.method, event.url, event.status, event.latency
+//!     );
+//! });
+//! ```
+
+use std::{
+    fmt::Debug,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use reqwest::{Method, Request, Response, StatusCode, Url, header::LOCATION};
+
+use crate::{
+    errors::session::SessionError,
+    session::Session,
+    utils::redact::{redact_body, redact_headers, redact_url},
+};
+
+/// 一次请求的观察结果，敏感信息均已脱敏
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RequestEvent {
+    /// 请求方法
+    pub method: Method,
+    /// 请求链接
+    pub url: String,
+    /// 请求头
+    pub request_headers: Vec<(String, String)>,
+    /// 请求体，流式请求体为`None`
+    pub request_body: Option<String>,
+    /// 第几次尝试发送该请求，从1开始
+    pub attempt: u32,
+    /// 响应状态码，请求失败时为`None`
+    pub status: Option<StatusCode>,
+    /// 响应头
+    pub response_headers: Vec<(String, String)>,
+    /// 重定向响应`Location`头中的目标链接
+    pub redirect: Option<String>,
+    /// 请求失败的原因
+    pub error: Option<String>,
+    /// 发送请求到收到响应头的耗时
+    pub latency: Duration,
+}
+
+/// 请求观察者
+///
+/// 该trait已为`Fn(&RequestEvent)`闭包实现
+pub trait Observer: Send + Sync {
+    /// 一次请求完成后调用
+    fn on_request(&self, event: &RequestEvent);
+}
+
+impl<F> Observer for F
+where
+    F: Fn(&RequestEvent) + Send + Sync,
+{
+    fn on_request(&self, event: &RequestEvent) {
+        self(event)
+    }
+}
+
+/// [`Session`]中保存的观察者
+#[derive(Clone, Default)]
+pub(crate) struct Observers(Vec<Arc<dyn Observer>>);
+
+impl Debug for Observers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Observers({})", self.0.len())
+    }
+}
+
+impl Observers {
+    /// 是否需要为请求生成[`RequestEvent`]
+    fn is_active(&self) -> bool {
+        cfg!(feature = "tracing") || !self.0.is_empty()
+    }
+
+    /// 在发送请求前记录请求信息，无需观察时返回`None`
+    pub(crate) fn start(&self, request: &Request, attempt: u32) -> Option<PendingEvent> {
+        self.is_active().then(|| PendingEvent {
+            method: request.method().clone(),
+            url: redact_url(request.url()),
+            raw_url: request.url().clone(),
+            request_headers: redact_headers(request.headers()),
+            request_body: request
+                .body()
+                .and_then(|body| body.as_bytes())
+                .map(|bytes| redact_body(&String::from_utf8_lossy(bytes))),
+            attempt,
+            start: Instant::now(),
+        })
+    }
+
+    /// 以请求结果通知全部观察者
+    pub(crate) fn finish(&self, pending: PendingEvent, result: &Result<Response, SessionError>) {
+        let event = pending.finish(result);
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            target: "rsmycqu::session",
+            method = %event.method,
+            url = %event.url,
+            attempt = event.attempt,
+            status = event.status.map(|status| status.as_u16()),
+            latency_ms = event.latency.as_millis() as u64,
+            redirect = event.redirect.as_deref(),
+            error = event.error.as_deref(),
+            "request finished"
+        );
+
+        self.0
+            .iter()
+            .for_each(|observer| observer.on_request(&event));
+    }
+}
+
+/// 已发送但尚未完成的请求
+pub(crate) struct PendingEvent {
+    method: Method,
+    url: String,
+    /// 未脱敏的链接，用于解析相对的重定向目标与在错误信息中替换为脱敏后的链接
+    raw_url: Url,
+    request_headers: Vec<(String, String)>,
+    request_body: Option<String>,
+    attempt: u32,
+    start: Instant,
+}
+
+impl PendingEvent {
+    fn finish(self, result: &Result<Response, SessionError>) -> RequestEvent {
+        let latency = self.start.elapsed();
+        let (status, response_headers, redirect, error) = match result {
+            Ok(response) => (
+                Some(response.status()),
+                redact_headers(response.headers()),
+                response
+                    .headers()
+                    .get(LOCATION)
+                    .and_then(|value| value.to_str().ok())
+                    .map(|location| match self.raw_url.join(location) {
+                        Ok(location) => redact_url(&location),
+                        Err(_) => location.to_string(),
+                    }),
+                None,
+            ),
+            Err(err) => (
+                None,
+                Vec::new(),
+                None,
+                Some(err.to_string().replace(self.raw_url.as_str(), &self.url)),
+            ),
+        };
+
+        RequestEvent {
+            method: self.method,
+            url: self.url,
+            request_headers: self.request_headers,
+            request_body: self.request_body,
+            attempt: self.attempt,
+            status,
+            response_headers,
+            redirect,
+            error,
+            latency,
+        }
+    }
+}
+
+impl Session {
+    /// 添加请求观察者，与该会话之后的所有克隆共享
+    pub fn add_observer(self, observer: impl Observer + 'static) -> Self {
+        let mut observers = self.observers.0;
+        observers.push(Arc::new(observer));
+        Session {
+            observers: Observers(observers),
+            ..self
+        }
+    }
+
+    /// 移除全部请求观察者
+    pub fn clear_observers(self) -> Self {
+        Session {
+            observers: Observers::default(),
+            ..self
+        }
+    }
+}
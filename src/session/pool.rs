@@ -0,0 +1,283 @@
+//! 按账号管理多个相互隔离的会话
+//!
+//! [`Session`]的克隆共享同一Cookie存储，为多个学生服务时若错误地克隆了会话，不同账号的Cookie会相互泄露。
+//! [`SessionPool`]以账号为键为每个账号持有一个独立创建的[`Session`]，并保证：
+//!
+//! - 同一账号的会话同一时刻只会被一个任务持有，登陆、获取访问权限等需要`&mut Session`的操作不会相互干扰
+//! - 不同账号的会话可以被多个`tokio`任务并发使用
+//! - 空闲超过[`SessionPool::set_idle_timeout`]设置时间的会话会被淘汰
+//!
+//! # Examples
+//! ```rust, no_run
+//! # use rsmycqu::session::{Client, pool::SessionPool};
+//! # use rsmycqu::sso::login;
+//! # use rsmycqu::mycqu::{access_mycqu, User};
+//! # async fn pool() {
+//! let client = Client::default();
+//! let pool = SessionPool::new();
+//!
+//! let mut session = pool.get("20200001").await;
+//! if !session.is_login() {
+//!     login(&client, &mut session, "20200001", "password", false).await.unwrap();
+//!     access_mycqu(&client, &mut session).await.unwrap();
+//! }
+//! let user = User::fetch_self(&client, &session).await.unwrap();
+//! # }
+//! ```
+
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    ops::{Deref, DerefMut},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use tokio::sync::{Mutex as AsyncMutex, OwnedMutexGuard};
+
+use crate::session::{Session, access_info::Service};
+
+type SessionFactory = Arc<dyn Fn() -> Session + Send + Sync>;
+
+/// 池中单个账号的会话
+struct PoolEntry {
+    session: Arc<AsyncMutex<Session>>,
+    last_used: Arc<Mutex<Instant>>,
+}
+
+impl PoolEntry {
+    fn new(session: Session) -> Self {
+        PoolEntry {
+            session: Arc::new(AsyncMutex::new(session)),
+            last_used: Arc::new(Mutex::new(Instant::now())),
+        }
+    }
+
+    fn idle(&self) -> Duration {
+        self.last_used.lock().unwrap().elapsed()
+    }
+
+    /// 会话当前是否被某个任务持有或等待
+    ///
+    /// 池外每个正在持有或等待该会话的任务都持有一份`session`的克隆
+    fn in_use(&self) -> bool {
+        Arc::strong_count(&self.session) > 1
+    }
+}
+
+/// 以账号为键的会话池，克隆间共享池中的会话
+#[derive(Clone)]
+pub struct SessionPool {
+    entries: Arc<Mutex<HashMap<String, PoolEntry>>>,
+    idle_timeout: Option<Duration>,
+    factory: SessionFactory,
+}
+
+impl Debug for SessionPool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SessionPool")
+            .field("len", &self.len())
+            .field("idle_timeout", &self.idle_timeout)
+            .finish()
+    }
+}
+
+impl Default for SessionPool {
+    fn default() -> Self {
+        SessionPool::new()
+    }
+}
+
+impl SessionPool {
+    /// 创建空的会话池，池中的会话由[`Session::new`]创建且不会因空闲被淘汰
+    pub fn new() -> Self {
+        SessionPool {
+            entries: Arc::default(),
+            idle_timeout: None,
+            factory: Arc::new(Session::new),
+        }
+    }
+
+    /// 设置会话的最长空闲时间，超过该时间未被使用的会话会在[`SessionPool::evict_idle`]或[`SessionPool::get`]时被淘汰
+    pub fn set_idle_timeout(self, idle_timeout: Duration) -> Self {
+        SessionPool {
+            idle_timeout: Some(idle_timeout),
+            ..self
+        }
+    }
+
+    /// 设置创建新会话的方式，可用于为池中的会话统一设置重试策略、限流器等
+    ///
+    /// `factory`每次调用都应当返回新创建的[`Session`]而非同一会话的克隆，否则不同账号将共享Cookie
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use rsmycqu::session::{Session, pool::SessionPool, rate_limit::{HostLimit, RateLimiter}};
+    /// let limiter = RateLimiter::new().set_default_limit(HostLimit::new().set_max_in_flight(8));
+    /// let pool = SessionPool::new()
+    ///     .set_session_factory(move || Session::new().set_rate_limiter(limiter.clone()));
+    /// ```
+    pub fn set_session_factory(
+        self,
+        factory: impl Fn() -> Session + Send + Sync + 'static,
+    ) -> Self {
+        SessionPool {
+            factory: Arc::new(factory),
+            ..self
+        }
+    }
+
+    /// 获取账号`account`的会话，账号不在池中时创建新的会话
+    ///
+    /// 返回的[`PooledSession`]被丢弃前，其他任务获取同一账号的会话时会等待
+    pub async fn get(&self, account: &str) -> PooledSession {
+        let (session, last_used) = {
+            let mut entries = self.entries.lock().unwrap();
+            self.evict_expired(&mut entries);
+            let entry = entries
+                .entry(account.to_string())
+                .or_insert_with(|| PoolEntry::new((self.factory)()));
+            *entry.last_used.lock().unwrap() = Instant::now();
+            (entry.session.clone(), entry.last_used.clone())
+        };
+
+        let guard = session.lock_owned().await;
+        *last_used.lock().unwrap() = Instant::now();
+        PooledSession {
+            account: account.to_string(),
+            guard,
+            last_used,
+        }
+    }
+
+    /// 将`session`作为账号`account`的会话放入池中，如由[`Session::restore`]恢复的会话
+    ///
+    /// 账号已在池中时替换原有会话，正在使用原有会话的任务不受影响
+    pub fn insert(&self, account: impl Into<String>, session: Session) {
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(account.into(), PoolEntry::new(session));
+    }
+
+    /// 从池中移除账号`account`的会话，返回该账号是否在池中
+    pub fn remove(&self, account: &str) -> bool {
+        self.entries.lock().unwrap().remove(account).is_some()
+    }
+
+    /// 账号`account`是否在池中
+    pub fn contains(&self, account: &str) -> bool {
+        self.entries.lock().unwrap().contains_key(account)
+    }
+
+    /// 池中的账号数量
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    /// 池是否为空
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 池中的全部账号
+    pub fn accounts(&self) -> Vec<String> {
+        self.entries.lock().unwrap().keys().cloned().collect()
+    }
+
+    /// 账号`account`的会话状态，账号不在池中时为`None`
+    ///
+    /// 会话正在被其他任务使用时会等待其释放
+    pub async fn state(&self, account: &str) -> Option<AccountState> {
+        let (session, last_used) = {
+            let entries = self.entries.lock().unwrap();
+            let entry = entries.get(account)?;
+            (entry.session.clone(), entry.last_used.clone())
+        };
+
+        let session = session.lock().await;
+        let idle = last_used.lock().unwrap().elapsed();
+        Some(AccountState {
+            is_login: session.is_login(),
+            services: session.services(),
+            idle,
+        })
+    }
+
+    /// 淘汰空闲时间超过最长空闲时间且未被使用的会话，返回被淘汰的账号
+    pub fn evict_idle(&self) -> Vec<String> {
+        self.evict_expired(&mut self.entries.lock().unwrap())
+    }
+
+    fn evict_expired(&self, entries: &mut HashMap<String, PoolEntry>) -> Vec<String> {
+        let Some(idle_timeout) = self.idle_timeout else {
+            return Vec::new();
+        };
+
+        let expired: Vec<String> = entries
+            .iter()
+            .filter(|(_, entry)| entry.idle() > idle_timeout && !entry.in_use())
+            .map(|(account, _)| account.clone())
+            .collect();
+        expired.iter().for_each(|account| {
+            entries.remove(account);
+        });
+        expired
+    }
+}
+
+/// 池中账号的会话状态
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct AccountState {
+    /// 是否已经通过[`sso`](crate::sso)登陆
+    pub is_login: bool,
+    /// 拥有访问信息的服务
+    pub services: Vec<Service>,
+    /// 距上次使用的时间
+    pub idle: Duration,
+}
+
+/// 从[`SessionPool`]中取出的会话，被丢弃时归还
+///
+/// 通过[`Deref`]与[`DerefMut`]当作[`Session`]使用
+pub struct PooledSession {
+    account: String,
+    guard: OwnedMutexGuard<Session>,
+    last_used: Arc<Mutex<Instant>>,
+}
+
+impl PooledSession {
+    /// 会话所属的账号
+    pub fn account(&self) -> &str {
+        &self.account
+    }
+}
+
+impl Debug for PooledSession {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PooledSession")
+            .field("account", &self.account)
+            .field("session", &*self.guard)
+            .finish()
+    }
+}
+
+impl Deref for PooledSession {
+    type Target = Session;
+
+    fn deref(&self) -> &Self::Target {
+        &self.guard
+    }
+}
+
+impl DerefMut for PooledSession {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.guard
+    }
+}
+
+impl Drop for PooledSession {
+    fn drop(&mut self) {
+        *self.last_used.lock().unwrap() = Instant::now();
+    }
+}
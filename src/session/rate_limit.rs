@@ -0,0 +1,201 @@
+//! 按主机限制请求速率与并发数
+//!
+//! 选课期间大量并发的查询请求会被教务网限流，为[`Session`]设置[`RateLimiter`]后，
+//! [`Session::execute`]会在发送请求前按请求的主机等待，使请求速率与同时进行中的请求数不超过[`HostLimit`]的限制
+//!
+//! [`RateLimiter`]的克隆共享同一限流状态，将同一个[`RateLimiter`]设置到多个[`Session`]即可在这些会话间共享限额
+//!
+//! # Examples
+//! ```rust
+//! # use rsmycqu::session::{Session, rate_limit::{HostLimit, RateLimiter}};
+//! let limiter = RateLimiter::new().set_host_limit(
+//!     "my.cqu.edu.cn",
+//!     HostLimit::new().set_requests_per_second(5).set_max_in_flight(4),
+//! );
+//! let session = Session::new().set_rate_limiter(limiter);
+//! ```
+
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tokio::{
+    sync::{OwnedSemaphorePermit, Semaphore},
+    time::Instant,
+};
+
+use crate::session::Session;
+
+/// 单个主机的请求限制
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct HostLimit {
+    requests_per_second: Option<u32>,
+    max_in_flight: Option<usize>,
+}
+
+impl HostLimit {
+    /// 创建不限制请求的[`HostLimit`]
+    pub fn new() -> Self {
+        HostLimit::default()
+    }
+
+    /// 设置每秒最多发出的请求数，为0时视为1
+    pub fn set_requests_per_second(self, requests_per_second: u32) -> Self {
+        HostLimit {
+            requests_per_second: Some(requests_per_second.max(1)),
+            ..self
+        }
+    }
+
+    /// 设置同时进行中的最大请求数，为0时视为1
+    pub fn set_max_in_flight(self, max_in_flight: usize) -> Self {
+        HostLimit {
+            max_in_flight: Some(max_in_flight.max(1)),
+            ..self
+        }
+    }
+
+    /// 每秒最多发出的请求数
+    pub fn requests_per_second(&self) -> Option<u32> {
+        self.requests_per_second
+    }
+
+    /// 同时进行中的最大请求数
+    pub fn max_in_flight(&self) -> Option<usize> {
+        self.max_in_flight
+    }
+}
+
+/// 按主机限制请求的限流器，克隆间共享限流状态
+#[derive(Clone, Default)]
+pub struct RateLimiter {
+    limits: HashMap<String, HostLimit>,
+    default_limit: Option<HostLimit>,
+    hosts: Arc<Mutex<HashMap<String, Arc<HostState>>>>,
+}
+
+impl Debug for RateLimiter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RateLimiter")
+            .field("limits", &self.limits)
+            .field("default_limit", &self.default_limit)
+            .finish()
+    }
+}
+
+impl RateLimiter {
+    /// 创建不限制任何主机的[`RateLimiter`]
+    pub fn new() -> Self {
+        RateLimiter::default()
+    }
+
+    /// 设置主机`host`（如`my.cqu.edu.cn`）的请求限制
+    ///
+    /// 设置后该限流器不再与设置前的克隆共享限流状态
+    pub fn set_host_limit(self, host: impl Into<String>, limit: HostLimit) -> Self {
+        let mut limits = self.limits;
+        limits.insert(host.into(), limit);
+        RateLimiter {
+            limits,
+            default_limit: self.default_limit,
+            hosts: Arc::default(),
+        }
+    }
+
+    /// 设置未单独配置的主机使用的请求限制
+    ///
+    /// 设置后该限流器不再与设置前的克隆共享限流状态
+    pub fn set_default_limit(self, limit: HostLimit) -> Self {
+        RateLimiter {
+            default_limit: Some(limit),
+            hosts: Arc::default(),
+            ..self
+        }
+    }
+
+    /// 主机`host`的请求限制
+    pub fn host_limit(&self, host: &str) -> Option<HostLimit> {
+        self.limits.get(host).copied().or(self.default_limit)
+    }
+
+    /// 等待直到可以向`host`发出请求，返回的[`RateLimitPermit`]被丢弃前该请求计入进行中的请求数
+    pub(crate) async fn acquire(&self, host: &str) -> RateLimitPermit {
+        let Some(state) = self.host_state(host) else {
+            return RateLimitPermit { _permit: None };
+        };
+
+        let permit = match &state.in_flight {
+            Some(semaphore) => Some(
+                semaphore
+                    .clone()
+                    .acquire_owned()
+                    .await
+                    .expect("semaphore in rate limiter should never be closed"),
+            ),
+            None => None,
+        };
+
+        if let Some(interval) = state.interval {
+            let slot = {
+                let mut next_slot = state.next_slot.lock().unwrap();
+                let slot = (*next_slot).max(Instant::now());
+                *next_slot = slot + interval;
+                slot
+            };
+            tokio::time::sleep_until(slot).await;
+        }
+
+        RateLimitPermit { _permit: permit }
+    }
+
+    fn host_state(&self, host: &str) -> Option<Arc<HostState>> {
+        let limit = self.host_limit(host)?;
+        let mut hosts = self.hosts.lock().unwrap();
+        let state = hosts.entry(host.to_string()).or_insert_with(|| {
+            Arc::new(HostState {
+                interval: limit
+                    .requests_per_second
+                    .map(|rps| Duration::from_secs(1) / rps),
+                next_slot: Mutex::new(Instant::now()),
+                in_flight: limit.max_in_flight.map(|n| Arc::new(Semaphore::new(n))),
+            })
+        });
+        Some(state.clone())
+    }
+}
+
+/// 单个主机的限流状态
+struct HostState {
+    /// 相邻两次请求的最小间隔
+    interval: Option<Duration>,
+    /// 下一个请求最早可以发出的时间
+    next_slot: Mutex<Instant>,
+    /// 进行中请求数的信号量
+    in_flight: Option<Arc<Semaphore>>,
+}
+
+/// 占用的进行中请求名额，被丢弃时释放
+pub(crate) struct RateLimitPermit {
+    _permit: Option<OwnedSemaphorePermit>,
+}
+
+impl Session {
+    /// 设置会话使用的限流器，与该会话的所有克隆共享
+    pub fn set_rate_limiter(self, rate_limiter: RateLimiter) -> Self {
+        Session {
+            rate_limiter: Some(rate_limiter),
+            ..self
+        }
+    }
+
+    /// 取消会话的请求限流
+    pub fn clear_rate_limiter(self) -> Self {
+        Session {
+            rate_limiter: None,
+            ..self
+        }
+    }
+}
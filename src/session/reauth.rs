@@ -0,0 +1,221 @@
+//! 服务访问权限过期时的自动重新认证
+//!
+//! 在[`Session`]上注册[`CredentialProvider`]后，[`mycqu`](crate::mycqu)与[`card`](crate::card)中的接口
+//! 在收到`401`响应时会自动重新登陆统一身份认证、重新获取对应服务的访问权限，并重放失败的请求一次
+//!
+//! 重新获取的访问信息会写回调用接口时传入的会话（包括以`&Session`调用的接口），之后的请求直接使用新的访问信息，
+//! 不会再次重新登陆；该会话的克隆拥有各自的访问信息，不受影响
+
+use std::{fmt::Debug, sync::Arc};
+
+#[cfg(any(feature = "mycqu", feature = "card"))]
+use crate::session::Client;
+use crate::session::{Session, access_info::Service};
+
+/// 统一身份认证登陆凭据
+#[derive(Clone, Eq, PartialEq)]
+pub struct Credentials {
+    /// 统一身份认证号或学工号
+    pub auth: String,
+    /// 密码
+    pub password: String,
+}
+
+impl Credentials {
+    /// 由账号与密码构建登陆凭据
+    pub fn new(auth: impl Into<String>, password: impl Into<String>) -> Self {
+        Credentials {
+            auth: auth.into(),
+            password: password.into(),
+        }
+    }
+}
+
+impl Debug for Credentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Credentials")
+            .field("auth", &self.auth)
+            .field("password", &"[REDACTED]")
+            .finish()
+    }
+}
+
+/// 重新认证时提供登陆凭据
+///
+/// 返回[`None`]时放弃本次重新认证，接口将照常返回[`ApiError::NotAccess`](crate::errors::ApiError::NotAccess)
+///
+/// 该trait已为[`Credentials`]及`Fn() -> Option<Credentials>`闭包实现
+pub trait CredentialProvider: Send + Sync {
+    /// 获取登陆凭据
+    fn credentials(&self) -> Option<Credentials>;
+}
+
+impl CredentialProvider for Credentials {
+    fn credentials(&self) -> Option<Credentials> {
+        Some(self.clone())
+    }
+}
+
+impl<F> CredentialProvider for F
+where
+    F: Fn() -> Option<Credentials> + Send + Sync,
+{
+    fn credentials(&self) -> Option<Credentials> {
+        self()
+    }
+}
+
+/// 重新认证过程中产生的事件，可通过[`Session::set_reauth_hook`]观察
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ReAuthEvent {
+    /// 收到`401`响应，开始重新认证
+    Started {
+        /// 需要重新认证的服务
+        service: Service,
+    },
+    /// 重新认证成功，即将重放失败的请求
+    Succeeded {
+        /// 重新认证的服务
+        service: Service,
+    },
+    /// 重新认证失败
+    Failed {
+        /// 重新认证的服务
+        service: Service,
+        /// 失败原因
+        reason: String,
+    },
+}
+
+type ReAuthHook = Arc<dyn Fn(&ReAuthEvent) + Send + Sync>;
+
+/// [`Session`]中保存的重新认证配置
+#[derive(Clone, Default)]
+pub(crate) struct ReAuth {
+    provider: Option<Arc<dyn CredentialProvider>>,
+    hook: Option<ReAuthHook>,
+}
+
+impl Debug for ReAuth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReAuth")
+            .field("provider", &self.provider.is_some())
+            .field("hook", &self.hook.is_some())
+            .finish()
+    }
+}
+
+#[cfg(any(feature = "mycqu", feature = "card"))]
+impl ReAuth {
+    fn notify(&self, event: ReAuthEvent) {
+        if let Some(hook) = self.hook.as_ref() {
+            hook(&event)
+        }
+    }
+}
+
+impl Session {
+    /// 注册登陆凭据提供者以启用自动重新认证
+    ///
+    /// 启用后，需要服务访问权限的接口在收到`401`响应时会使用提供的凭据重新登陆并获取访问权限，
+    /// 随后重放失败的请求一次；新获取的访问信息会写回该会话，见[`reauth`](crate::session::reauth)
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use rsmycqu::session::{Session, reauth::Credentials};
+    /// let session = Session::new()
+    ///     .set_credential_provider(Credentials::new("your_auth", "your_password"))
+    ///     .set_reauth_hook(|event| println!("{event:?}"));
+    /// ```
+    pub fn set_credential_provider(self, provider: impl CredentialProvider + 'static) -> Self {
+        Session {
+            reauth: ReAuth {
+                provider: Some(Arc::new(provider)),
+                ..self.reauth
+            },
+            ..self
+        }
+    }
+
+    /// 设置观察重新认证过程的回调
+    pub fn set_reauth_hook(self, hook: impl Fn(&ReAuthEvent) + Send + Sync + 'static) -> Self {
+        Session {
+            reauth: ReAuth {
+                hook: Some(Arc::new(hook)),
+                ..self.reauth
+            },
+            ..self
+        }
+    }
+
+    /// 移除已注册的登陆凭据提供者，关闭自动重新认证
+    pub fn clear_credential_provider(self) -> Self {
+        Session {
+            reauth: ReAuth {
+                provider: None,
+                ..self.reauth
+            },
+            ..self
+        }
+    }
+}
+
+/// 使用注册的凭据重新登陆并获取`service`的访问权限，成功时返回拥有新访问信息的会话
+///
+/// 返回的会话是`session`的克隆，`session`本身的访问信息不会被修改
+#[cfg(any(feature = "mycqu", feature = "card"))]
+pub(crate) async fn reauthenticate(
+    client: &Client,
+    session: &Session,
+    service: Service,
+) -> Option<Session> {
+    let reauth = &session.reauth;
+    let provider = reauth.provider.as_ref()?;
+
+    reauth.notify(ReAuthEvent::Started { service });
+
+    let result = async {
+        let credentials = provider
+            .credentials()
+            .ok_or_else(|| "No credentials provided".to_string())?;
+
+        let mut fresh = session.clone();
+        match crate::sso::login(
+            client,
+            &mut fresh,
+            &credentials.auth,
+            &credentials.password,
+            false,
+        )
+        .await
+        {
+            Ok(crate::sso::LoginResult::Success) => {}
+            Ok(other) => return Err(format!("Login failed: {other:?}")),
+            Err(err) => return Err(err.to_string()),
+        }
+
+        match service {
+            #[cfg(feature = "mycqu")]
+            Service::MyCQU => crate::mycqu::access_mycqu(client, &mut fresh)
+                .await
+                .map_err(|err| err.to_string())?,
+            #[cfg(feature = "card")]
+            Service::Card => crate::card::access_card(client, &mut fresh)
+                .await
+                .map_err(|err| err.to_string())?,
+        }
+        Ok(fresh)
+    }
+    .await;
+
+    match result {
+        Ok(fresh) => {
+            reauth.notify(ReAuthEvent::Succeeded { service });
+            Some(fresh)
+        }
+        Err(reason) => {
+            reauth.notify(ReAuthEvent::Failed { service, reason });
+            None
+        }
+    }
+}
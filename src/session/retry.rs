@@ -0,0 +1,204 @@
+//! 请求失败时的重试策略
+//!
+//! 选课高峰期教务网与校园卡网站经常超时或返回`5xx`，为[`Session`]设置[`RetryPolicy`]后，
+//! [`Session::execute`]会在请求遇到可重试的错误或状态码时按指数退避等待后重新发送请求
+//!
+//! 为避免重复提交登陆等非幂等请求，默认仅重试幂等方法（`GET`、`HEAD`、`PUT`、`DELETE`、`OPTIONS`、`TRACE`）的请求；
+//! 连接建立失败时请求尚未到达服务器，因此任何方法的请求都会被重试
+//!
+//! # Examples
+//! ```rust
+//! # use std::time::Duration;
+//! # use rsmycqu::session::{Session, retry::RetryPolicy};
+//! let session = Session::new().set_retry_policy(
+//!     RetryPolicy::default()
+//!         .set_max_attempts(5)
+//!         .set_base_delay(Duration::from_millis(500)),
+//! );
+//! ```
+
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    time::Duration,
+};
+
+use reqwest::{Method, Response, StatusCode, header::RETRY_AFTER};
+
+use crate::{errors::session::SessionError, session::Session};
+
+/// 请求重试策略
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    jitter: bool,
+    retryable_statuses: Vec<StatusCode>,
+    retry_timeout: bool,
+    retry_connect: bool,
+    retry_non_idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    /// 最多尝试3次，退避从200毫秒开始、不超过5秒并带有随机抖动，
+    /// 重试`429`、`502`、`503`、`504`状态码、超时与连接错误，不重试非幂等请求
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(200),
+            max_delay: Duration::from_secs(5),
+            jitter: true,
+            retryable_statuses: vec![
+                StatusCode::TOO_MANY_REQUESTS,
+                StatusCode::BAD_GATEWAY,
+                StatusCode::SERVICE_UNAVAILABLE,
+                StatusCode::GATEWAY_TIMEOUT,
+            ],
+            retry_timeout: true,
+            retry_connect: true,
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// 不进行任何重试的策略，为[`Session`]的默认策略
+    pub fn none() -> Self {
+        RetryPolicy {
+            max_attempts: 1,
+            ..RetryPolicy::default()
+        }
+    }
+
+    /// 设置最大尝试次数（包括第一次请求），小于1时视为1
+    pub fn set_max_attempts(self, max_attempts: u32) -> Self {
+        RetryPolicy {
+            max_attempts: max_attempts.max(1),
+            ..self
+        }
+    }
+
+    /// 设置第一次重试前的退避时间，之后每次重试翻倍
+    pub fn set_base_delay(self, base_delay: Duration) -> Self {
+        RetryPolicy { base_delay, ..self }
+    }
+
+    /// 设置退避时间的上限，同样限制响应中`Retry-After`指定的等待时间
+    pub fn set_max_delay(self, max_delay: Duration) -> Self {
+        RetryPolicy { max_delay, ..self }
+    }
+
+    /// 设置是否为退避时间添加随机抖动，启用时实际等待时间在退避时间的一半到全部之间
+    pub fn set_jitter(self, jitter: bool) -> Self {
+        RetryPolicy { jitter, ..self }
+    }
+
+    /// 设置需要重试的响应状态码
+    pub fn set_retryable_statuses(self, statuses: impl IntoIterator<Item = StatusCode>) -> Self {
+        RetryPolicy {
+            retryable_statuses: statuses.into_iter().collect(),
+            ..self
+        }
+    }
+
+    /// 设置是否重试超时的请求
+    pub fn set_retry_timeout(self, retry_timeout: bool) -> Self {
+        RetryPolicy {
+            retry_timeout,
+            ..self
+        }
+    }
+
+    /// 设置是否重试连接建立失败的请求
+    pub fn set_retry_connect(self, retry_connect: bool) -> Self {
+        RetryPolicy {
+            retry_connect,
+            ..self
+        }
+    }
+
+    /// 设置是否在超时或收到可重试状态码时重试`POST`等非幂等请求
+    pub fn set_retry_non_idempotent(self, retry_non_idempotent: bool) -> Self {
+        RetryPolicy {
+            retry_non_idempotent,
+            ..self
+        }
+    }
+
+    /// 最大尝试次数
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// 判断第`attempt`次请求的结果是否需要重试，需要时返回重试前的等待时间
+    pub(crate) fn retry_after(
+        &self,
+        attempt: u32,
+        method: &Method,
+        result: &Result<Response, SessionError>,
+    ) -> Option<Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+
+        let replayable = self.retry_non_idempotent || is_idempotent(method);
+        let retry = match result {
+            Ok(response) => replayable && self.retryable_statuses.contains(&response.status()),
+            Err(SessionError::Build { source }) if source.is_connect() => self.retry_connect,
+            Err(SessionError::Build { source }) if source.is_timeout() => {
+                replayable && self.retry_timeout
+            }
+            Err(_) => false,
+        };
+        if !retry {
+            return None;
+        }
+
+        let retry_after = result
+            .as_ref()
+            .ok()
+            .and_then(|response| response.headers().get(RETRY_AFTER))
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse().ok())
+            .map(Duration::from_secs);
+
+        Some(
+            retry_after
+                .map(|delay| delay.min(self.max_delay))
+                .unwrap_or_else(|| self.backoff(attempt)),
+        )
+    }
+
+    /// 第`attempt`次请求失败后的退避时间
+    pub(crate) fn backoff(&self, attempt: u32) -> Duration {
+        let delay = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max_delay);
+        if !self.jitter {
+            return delay;
+        }
+
+        let half = delay / 2;
+        let random = RandomState::new().build_hasher().finish();
+        half + half.mul_f64(random as f64 / u64::MAX as f64)
+    }
+}
+
+fn is_idempotent(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::GET | Method::HEAD | Method::PUT | Method::DELETE | Method::OPTIONS | Method::TRACE
+    )
+}
+
+impl Session {
+    /// 设置请求失败时的重试策略，默认不重试
+    pub fn set_retry_policy(self, retry_policy: RetryPolicy) -> Self {
+        Session {
+            retry_policy,
+            ..self
+        }
+    }
+}
@@ -0,0 +1,696 @@
+use std::{
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use reqwest::{Request, Response, Url};
+use rstest::*;
+
+use crate::{
+    errors::session::SessionError,
+    mycqu::User,
+    session::{
+        Client, CookieStore, Endpoints, Session, SessionSnapshot,
+        access_info::{AccessInfos, MyCQUAccessInfo, Service},
+        cassette::{Cassette, CassetteMode, Interaction, RecordedRequest, RecordedResponse},
+        observer::RequestEvent,
+        pool::SessionPool,
+        rate_limit::{HostLimit, RateLimiter},
+        reauth::Credentials,
+        retry::RetryPolicy,
+        transport::{ReqwestTransport, Transport, TransportFuture},
+    },
+    utils::redact::{redact_body, redact_headers, redact_url},
+};
+
+#[rstest]
+fn test_snapshot_restore_cookies() {
+    let session = Session::new();
+    let sso_url = Url::parse("https://sso.cqu.edu.cn/login").unwrap();
+    let mycqu_url = Url::parse("https://my.cqu.edu.cn/authserver/simple-user").unwrap();
+    session
+        .cookie_jar
+        .add_cookie_str("TGC=tgc-value; Path=/; HttpOnly", &sso_url);
+    session
+        .cookie_jar
+        .add_cookie_str("SESSION=mycqu-session; Path=/", &mycqu_url);
+
+    let stored = serde_json::to_string(&session.snapshot()).unwrap();
+    let snapshot: SessionSnapshot = serde_json::from_str(&stored).unwrap();
+    let restored = Session::restore(snapshot);
+
+    assert_eq!(
+        restored.cookie_jar.cookies(&sso_url),
+        session.cookie_jar.cookies(&sso_url)
+    );
+    assert_eq!(
+        restored.cookie_jar.cookies(&mycqu_url),
+        session.cookie_jar.cookies(&mycqu_url)
+    );
+    assert_eq!(restored.is_login(), session.is_login());
+    assert_eq!(restored.access_infos(), session.access_infos());
+}
+
+#[rstest]
+fn test_restored_session_is_isolated() {
+    let session = Session::new();
+    let restored = Session::restore(session.snapshot());
+    let url = Url::parse("http://card.cqu.edu.cn/Page/Page").unwrap();
+
+    restored.cookie_jar.add_cookie_str("hallticket=abc", &url);
+
+    assert!(session.cookie_jar.cookies(&url).is_none());
+    assert!(restored.cookie_jar.cookies(&url).is_some());
+}
+
+#[rstest]
+fn test_endpoints_join_ignore_trailing_slash() {
+    let endpoints = Endpoints::with_root("http://127.0.0.1:8080/");
+
+    assert_eq!(endpoints.sso_url("/login"), "http://127.0.0.1:8080/login");
+    assert_eq!(
+        Endpoints::default().sso_url("/login"),
+        "https://sso.cqu.edu.cn/login"
+    );
+}
+
+#[rstest]
+fn test_credentials_debug_redact_password() {
+    let credentials = Credentials::new("20200001", "secret-password");
+
+    assert!(!format!("{credentials:?}").contains("secret-password"));
+}
+
+#[rstest]
+fn test_access_infos_independent_between_clones() {
+    let session = Session::new();
+    let mut cloned = session.clone();
+
+    cloned.access_infos.get_mut().mycqu_access_info = Some(MyCQUAccessInfo::new("token"));
+
+    assert!(session.access_infos().mycqu_access_info.is_none());
+    assert!(cloned.access_infos().mycqu_access_info.is_some());
+}
+
+#[rstest]
+fn test_redact_url() {
+    let url = Url::parse(
+        "https://my.cqu.edu.cn/authserver/callback?ticket=ST-123&code=abc&service=https%3A%2F%2Fmy.cqu.edu.cn",
+    )
+    .unwrap();
+    assert_eq!(
+        redact_url(&url),
+        "https://my.cqu.edu.cn/authserver/callback?ticket=REDACTED&code=REDACTED&service=https%3A%2F%2Fmy.cqu.edu.cn"
+    );
+}
+
+#[rstest]
+fn test_redact_headers() {
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert("authorization", "Bearer secret".parse().unwrap());
+    headers.insert("cookie", "SESSION=abc".parse().unwrap());
+    headers.insert(
+        "set-cookie",
+        "TGC=tgc-value; Path=/; HttpOnly".parse().unwrap(),
+    );
+    headers.insert("content-type", "application/json".parse().unwrap());
+    headers.insert("location", "/cas/login?ticket=ST-1&x=1".parse().unwrap());
+
+    let redacted = redact_headers(&headers);
+    let get = |name: &str| {
+        redacted
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    };
+    assert_eq!(get("authorization"), Some("REDACTED"));
+    assert_eq!(get("cookie"), Some("REDACTED"));
+    assert_eq!(get("set-cookie"), Some("TGC=REDACTED; Path=/; HttpOnly"));
+    assert_eq!(get("content-type"), Some("application/json"));
+    assert_eq!(get("location"), Some("/cas/login?ticket=REDACTED&x=1"));
+}
+
+#[rstest]
+#[case::form(
+    "username=abc&password=123&croypto=xyz",
+    "username=abc&password=REDACTED&croypto=REDACTED"
+)]
+#[case::json(
+    r#"{"data":{"access_token":"abc","name":"n"}}"#,
+    r#"{"data":{"access_token":"REDACTED","name":"n"}}"#
+)]
+#[case::nested_json(
+    r#""{\"refresh_token\":\"abc\"}""#,
+    r#""{\"refresh_token\":\"REDACTED\"}""#
+)]
+#[case::html("<a href='?ticket=abc'>", "<a href='?ticket=abc'>")]
+fn test_redact_body(#[case] body: &str, #[case] expected: &str) {
+    assert_eq!(redact_body(body), expected);
+}
+
+#[rstest]
+fn test_cassette_json_roundtrip() {
+    let cassette = Cassette {
+        interactions: vec![Interaction {
+            request: RecordedRequest {
+                method: "GET".to_string(),
+                url: "https://my.cqu.edu.cn/authserver/simple-user".to_string(),
+                body: None,
+            },
+            response: RecordedResponse {
+                status: 200,
+                headers: vec![("content-type".to_string(), "application/json".to_string())],
+                body: "{}".to_string(),
+            },
+        }],
+    };
+
+    assert_eq!(Cassette::from_json(&cassette.to_json()).unwrap(), cassette);
+
+    let session = Session::new().set_cassette(cassette.clone(), CassetteMode::Replay);
+    assert_eq!(session.clone().cassette(), Some(cassette));
+    assert_eq!(session.clear_cassette().cassette(), None);
+}
+
+fn fake_mycqu(request: Request) -> Result<Response, SessionError> {
+    let authorized = request
+        .headers()
+        .get("authorization")
+        .is_some_and(|value| value == "Bearer token");
+    let response = if authorized {
+        http::Response::builder()
+            .header("set-cookie", "SESSION=fake; Path=/")
+            .body(
+                r#"{"name":"张三","username":"auth","code":"20200001","type":"student","email":null,"phoneNumber":null}"#,
+            )
+    } else {
+        http::Response::builder().status(401).body("")
+    };
+
+    Ok(response.unwrap().into())
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_in_memory_transport() {
+    let mut session = Session::new().set_transport(fake_mycqu);
+    session.access_infos.get_mut().mycqu_access_info = Some(MyCQUAccessInfo::new("token"));
+
+    let user = User::fetch_self(&Client::default(), &session)
+        .await
+        .unwrap();
+    assert_eq!(user.name, "张三");
+
+    // 传输层返回的Cookie同样会写入会话
+    let url = Url::parse("http://no.url.provided.local/").unwrap();
+    assert!(session.cookie_jar.cookies(&url).is_some());
+}
+
+/// 统计请求次数的中间件
+struct Counting<T> {
+    inner: T,
+    count: Arc<AtomicUsize>,
+}
+
+impl<T: Transport> Transport for Counting<T> {
+    fn execute<'a>(&'a self, client: &'a reqwest::Client, request: Request) -> TransportFuture<'a> {
+        self.count.fetch_add(1, Ordering::SeqCst);
+        self.inner.execute(client, request)
+    }
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_transport_middleware() {
+    let count = Arc::new(AtomicUsize::new(0));
+    let mut session = Session::new().set_transport(Counting {
+        inner: fake_mycqu,
+        count: count.clone(),
+    });
+    let client = Client::default();
+
+    assert!(User::fetch_self(&client, &session).await.is_err());
+    assert_eq!(count.load(Ordering::SeqCst), 0);
+
+    session.access_infos.get_mut().mycqu_access_info = Some(MyCQUAccessInfo::new("expired"));
+    assert!(User::fetch_self(&client, &session).await.is_err());
+    assert_eq!(count.load(Ordering::SeqCst), 1);
+
+    let session = session.clear_transport().set_cassette(
+        Cassette {
+            interactions: vec![],
+        },
+        CassetteMode::Replay,
+    );
+    assert!(User::fetch_self(&client, &session).await.is_err());
+    assert_eq!(count.load(Ordering::SeqCst), 1);
+}
+
+#[rstest]
+fn test_retry_backoff() {
+    let policy = RetryPolicy::default()
+        .set_base_delay(Duration::from_millis(100))
+        .set_max_delay(Duration::from_secs(1))
+        .set_jitter(false);
+    assert_eq!(policy.backoff(1), Duration::from_millis(100));
+    assert_eq!(policy.backoff(2), Duration::from_millis(200));
+    assert_eq!(policy.backoff(5), Duration::from_secs(1));
+
+    let policy = policy.set_jitter(true);
+    for attempt in 1..5 {
+        let delay = policy.backoff(attempt);
+        let expected = policy.clone().set_jitter(false).backoff(attempt);
+        assert!(delay >= expected / 2 && delay <= expected);
+    }
+}
+
+/// 前两次请求返回`503`的传输层
+fn flaky_transport(count: Arc<AtomicUsize>) -> impl Transport {
+    move |_: Request| -> Result<Response, SessionError> {
+        let status = match count.fetch_add(1, Ordering::SeqCst) {
+            0 | 1 => 503,
+            _ => 200,
+        };
+        Ok(http::Response::builder()
+            .status(status)
+            .body("")
+            .unwrap()
+            .into())
+    }
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_retry_retryable_status() {
+    let client = Client::default();
+    let policy = RetryPolicy::default().set_base_delay(Duration::from_millis(1));
+
+    let count = Arc::new(AtomicUsize::new(0));
+    let session = Session::new()
+        .set_transport(flaky_transport(count.clone()))
+        .set_retry_policy(policy.clone());
+    let res = session
+        .execute(client.get("https://my.cqu.edu.cn/"))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), 200);
+    assert_eq!(count.load(Ordering::SeqCst), 3);
+
+    // 非幂等请求不会因为状态码被重试
+    let count = Arc::new(AtomicUsize::new(0));
+    let session = session.set_transport(flaky_transport(count.clone()));
+    let res = session
+        .execute(client.post("https://my.cqu.edu.cn/"))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), 503);
+    assert_eq!(count.load(Ordering::SeqCst), 1);
+
+    let session = session.set_retry_policy(policy.set_retry_non_idempotent(true));
+    let res = session
+        .execute(client.post("https://my.cqu.edu.cn/"))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), 200);
+
+    // 默认不重试
+    let count = Arc::new(AtomicUsize::new(0));
+    let session = Session::new().set_transport(flaky_transport(count.clone()));
+    session
+        .execute(client.get("https://my.cqu.edu.cn/"))
+        .await
+        .unwrap();
+    assert_eq!(count.load(Ordering::SeqCst), 1);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_retry_connect_error() {
+    let count = Arc::new(AtomicUsize::new(0));
+    let session = Session::new()
+        .set_transport(Counting {
+            inner: ReqwestTransport,
+            count: count.clone(),
+        })
+        .set_retry_policy(
+            RetryPolicy::default()
+                .set_max_attempts(2)
+                .set_base_delay(Duration::from_millis(1)),
+        );
+
+    // 连接失败时请求未到达服务器，非幂等请求同样会被重试
+    let res = session
+        .execute(Client::default().post("http://127.0.0.1:1/"))
+        .await;
+    assert!(matches!(res.unwrap_err(), SessionError::Build { .. }));
+    assert_eq!(count.load(Ordering::SeqCst), 2);
+}
+
+/// 记录同时进行中请求数峰值的传输层
+#[derive(Default)]
+struct Slow {
+    current: AtomicUsize,
+    peak: AtomicUsize,
+}
+
+impl Transport for Slow {
+    fn execute<'a>(&'a self, _: &'a reqwest::Client, _: Request) -> TransportFuture<'a> {
+        Box::pin(async move {
+            let current = self.current.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(current, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            self.current.fetch_sub(1, Ordering::SeqCst);
+            Ok(http::Response::new("").into())
+        })
+    }
+}
+
+impl Transport for Arc<Slow> {
+    fn execute<'a>(&'a self, client: &'a reqwest::Client, request: Request) -> TransportFuture<'a> {
+        self.as_ref().execute(client, request)
+    }
+}
+
+async fn execute_concurrently(session: &Session, urls: &[&str]) {
+    let client = Client::default();
+    let handles: Vec<_> = urls
+        .iter()
+        .map(|url| {
+            let session = session.clone();
+            let builder = client.get(*url);
+            tokio::spawn(async move { session.execute(builder).await })
+        })
+        .collect();
+    for handle in handles {
+        handle.await.unwrap().unwrap();
+    }
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_rate_limit_max_in_flight() {
+    let transport = Arc::new(Slow::default());
+    let session = Session::new()
+        .set_transport(transport.clone())
+        .set_rate_limiter(
+            RateLimiter::new()
+                .set_host_limit("my.cqu.edu.cn", HostLimit::new().set_max_in_flight(2)),
+        );
+
+    execute_concurrently(&session, &["https://my.cqu.edu.cn/"; 6]).await;
+    assert_eq!(transport.peak.load(Ordering::SeqCst), 2);
+
+    // 未配置的主机不受限制
+    let transport = Arc::new(Slow::default());
+    let session = session.set_transport(transport.clone());
+    execute_concurrently(&session, &["https://card.cqu.edu.cn/"; 6]).await;
+    assert_eq!(transport.peak.load(Ordering::SeqCst), 6);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_rate_limit_requests_per_second() {
+    let limiter =
+        RateLimiter::new().set_default_limit(HostLimit::new().set_requests_per_second(20));
+    let session = Session::new()
+        .set_transport(Arc::new(Slow::default()))
+        .set_rate_limiter(limiter.clone());
+    // 共享同一限流器的另一个会话
+    let other = Session::new()
+        .set_transport(Arc::new(Slow::default()))
+        .set_rate_limiter(limiter);
+
+    let start = Instant::now();
+    tokio::join!(
+        execute_concurrently(&session, &["https://my.cqu.edu.cn/"; 3]),
+        execute_concurrently(&other, &["https://my.cqu.edu.cn/"; 2]),
+    );
+    // 5个请求间至少间隔4个50毫秒
+    assert!(start.elapsed() >= Duration::from_millis(200));
+}
+
+/// 收集请求事件的观察者
+fn collect_events(session: Session) -> (Session, Arc<Mutex<Vec<RequestEvent>>>) {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let collected = events.clone();
+    let session = session
+        .add_observer(move |event: &RequestEvent| collected.lock().unwrap().push(event.clone()));
+    (session, events)
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_observer_redacts_secrets() {
+    let transport = |_: Request| -> Result<Response, SessionError> {
+        Ok(http::Response::builder()
+            .status(302)
+            .header("Location", "/login?ticket=ST-secret")
+            .header("Set-Cookie", "synjones-auth=secret_token; Path=/")
+            .body("")
+            .unwrap()
+            .into())
+    };
+    let (session, events) = collect_events(Session::new().set_transport(transport));
+
+    session
+        .execute(
+            Client::default()
+                .post("https://sso.cqu.edu.cn/login?service=https://my.cqu.edu.cn")
+                .header("Authorization", "Bearer secret_token")
+                .header("Cookie", "synjones-auth=secret_token")
+                .form(&[("username", "20200001"), ("password", "encrypted_secret")]),
+        )
+        .await
+        .unwrap();
+
+    let events = events.lock().unwrap();
+    assert_eq!(events.len(), 1);
+    let event = &events[0];
+    assert_eq!(event.method, reqwest::Method::POST);
+    assert_eq!(event.status, Some(reqwest::StatusCode::FOUND));
+    assert_eq!(event.attempt, 1);
+    assert_eq!(
+        event.redirect.as_deref(),
+        Some("https://sso.cqu.edu.cn/login?ticket=REDACTED")
+    );
+    assert_eq!(
+        event.request_body.as_deref(),
+        Some("username=20200001&password=REDACTED")
+    );
+    assert!(!format!("{event:?}").contains("secret"));
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_observer_reports_retries_and_errors() {
+    let (session, events) = collect_events(
+        Session::new().set_retry_policy(
+            RetryPolicy::default()
+                .set_max_attempts(2)
+                .set_base_delay(Duration::from_millis(1)),
+        ),
+    );
+
+    assert!(
+        session
+            .execute(Client::default().get("http://127.0.0.1:1/?ticket=ST-secret"))
+            .await
+            .is_err()
+    );
+
+    {
+        let events = events.lock().unwrap();
+        assert_eq!(
+            events.iter().map(|event| event.attempt).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        for event in events.iter() {
+            assert_eq!(event.status, None);
+            assert!(event.error.is_some());
+            assert!(!format!("{event:?}").contains("ST-secret"));
+        }
+    }
+
+    // 清除后不再通知
+    let session = session.clear_observers();
+    let _ = session
+        .execute(Client::default().get("http://127.0.0.1:1/"))
+        .await;
+    assert_eq!(events.lock().unwrap().len(), 2);
+}
+
+#[rstest]
+fn test_mycqu_access_info_lifetime() {
+    // 旧版本快照中的登陆信息不包含令牌有效期
+    let access_info: MyCQUAccessInfo = serde_json::from_str(r#"{"auth_header":"token"}"#).unwrap();
+    assert_eq!(access_info, MyCQUAccessInfo::new("token"));
+    assert_eq!(access_info.remaining(), None);
+    assert!(!access_info.is_expired());
+    assert!(!access_info.can_refresh());
+
+    let access_info =
+        MyCQUAccessInfo::issued_now("token".to_string(), Some(60), Some("refresh".to_string()));
+    assert!(access_info.remaining().unwrap() > Duration::from_secs(50));
+    assert!(access_info.can_refresh());
+
+    let expired = MyCQUAccessInfo {
+        issued_at: Some(0),
+        ..access_info
+    };
+    assert_eq!(expired.remaining(), Some(Duration::ZERO));
+    assert!(expired.is_expired());
+
+    let mut session = Session::new();
+    session.access_infos.get_mut().mycqu_access_info = Some(expired);
+    assert_eq!(session.mycqu_access_remaining(), Some(Duration::ZERO));
+}
+
+#[rstest]
+fn test_access_infos_handoff() {
+    let access_info = MyCQUAccessInfo::new("token")
+        .set_expiry(std::time::SystemTime::now(), Duration::from_secs(3600))
+        .set_refresh_token("refresh");
+    assert_eq!(access_info.auth_header(), "token");
+    assert_eq!(access_info.refresh_token(), Some("refresh"));
+    assert_eq!(access_info.expires_in(), Some(Duration::from_secs(3600)));
+
+    // 登陆机器上获取的访问信息交给另一台机器上的会话使用
+    let infos = AccessInfos::new().set_mycqu(access_info.clone());
+    let mut session = Session::new().set_access_infos(infos);
+    assert!(session.has_access(Service::MyCQU));
+    assert_eq!(session.services(), vec![Service::MyCQU]);
+    assert_eq!(session.access_infos().mycqu(), Some(&access_info));
+
+    // 只移除该会话的访问信息，克隆不受影响
+    let cloned = session.clone();
+    session.clear_access(Service::MyCQU);
+    assert!(!session.has_access(Service::MyCQU));
+    assert!(session.services().is_empty());
+    assert!(cloned.has_access(Service::MyCQU));
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_session_pool_isolation() {
+    let pool = SessionPool::new();
+    let url = Url::parse("https://sso.cqu.edu.cn/login").unwrap();
+
+    {
+        let mut session = pool.get("a").await;
+        session.cookie_jar.add_cookie_str("TGC=a; Path=/", &url);
+        session.is_login = true;
+        session.access_infos.get_mut().mycqu_access_info = Some(MyCQUAccessInfo::new("a"));
+    }
+
+    let session = pool.get("b").await;
+    assert_eq!(session.account(), "b");
+    assert!(session.cookie_jar.cookies(&url).is_none());
+    assert!(!session.is_login());
+    drop(session);
+
+    let state = pool.state("a").await.unwrap();
+    assert!(state.is_login);
+    assert_eq!(state.services, vec![Service::MyCQU]);
+    assert!(pool.get("a").await.cookie_jar.cookies(&url).is_some());
+
+    let mut accounts = pool.accounts();
+    accounts.sort();
+    assert_eq!(accounts, vec!["a", "b"]);
+    assert!(pool.remove("b"));
+    assert!(pool.state("b").await.is_none());
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_session_pool_serializes_account_access() {
+    let pool = SessionPool::new();
+    let current = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+
+    let handles: Vec<_> = (0..8)
+        .map(|i| {
+            let pool = pool.clone();
+            let current = current.clone();
+            let peak = peak.clone();
+            tokio::spawn(async move {
+                let _session = pool.get(if i % 2 == 0 { "a" } else { "b" }).await;
+                let now = current.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                current.fetch_sub(1, Ordering::SeqCst);
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.await.unwrap();
+    }
+
+    // 每个账号同一时刻只被一个任务持有
+    assert!(peak.load(Ordering::SeqCst) <= 2);
+    assert_eq!(pool.len(), 2);
+
+    // 不同账号的会话可以同时被持有：两个任务都持有会话后才能通过屏障
+    let barrier = Arc::new(tokio::sync::Barrier::new(2));
+    let holders: Vec<_> = ["a", "b"]
+        .into_iter()
+        .map(|account| {
+            let pool = pool.clone();
+            let barrier = barrier.clone();
+            tokio::spawn(async move {
+                let _session = pool.get(account).await;
+                barrier.wait().await;
+            })
+        })
+        .collect();
+    for holder in holders {
+        tokio::time::timeout(Duration::from_secs(5), holder)
+            .await
+            .expect("sessions of different accounts should be held concurrently")
+            .unwrap();
+    }
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_session_pool_keeps_waiting_entries() {
+    let pool = SessionPool::new().set_idle_timeout(Duration::ZERO);
+    let session = pool.get("a").await;
+
+    let waiter = {
+        let pool = pool.clone();
+        tokio::spawn(async move { pool.get("a").await.account().to_string() })
+    };
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    drop(session);
+
+    // 等待中的任务仍持有该账号的会话，不会被淘汰
+    assert!(pool.evict_idle().is_empty());
+    assert_eq!(waiter.await.unwrap(), "a");
+    assert!(pool.contains("a"));
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_session_pool_evict_idle() {
+    let pool = SessionPool::new()
+        .set_idle_timeout(Duration::from_millis(20))
+        .set_session_factory(|| Session::new().set_retry_policy(RetryPolicy::default()));
+    pool.insert("idle", Session::new());
+    let in_use = pool.get("in_use").await;
+    assert_eq!(in_use.retry_policy, RetryPolicy::default());
+
+    tokio::time::sleep(Duration::from_millis(30)).await;
+    assert_eq!(pool.evict_idle(), vec!["idle".to_string()]);
+    assert!(pool.contains("in_use"));
+
+    drop(in_use);
+    pool.get("fresh").await;
+    assert!(pool.evict_idle().is_empty());
+    tokio::time::sleep(Duration::from_millis(30)).await;
+    // 获取会话时同样会淘汰空闲的会话
+    pool.get("fresh").await;
+    assert_eq!(pool.accounts(), vec!["fresh".to_string()]);
+}
@@ -0,0 +1,99 @@
+//! 可替换的HTTP传输层
+//!
+//! [`Session::execute`]发出的全部请求都会经过[`Transport`]发送，默认使用[`ReqwestTransport`]，
+//! 可通过[`Session::set_transport`]替换为内存中的假传输层以进行单元测试，或包装已有传输层以实现日志、签名等中间件
+//!
+//! # Examples
+//! ```rust
+//! # use rsmycqu::session::{Session, reqwest, transport::{ReqwestTransport, Transport, TransportFuture}};
+//! /// 打印全部请求的中间件
+//! struct Logging<T>(T);
+//!
+//! impl<T: Transport> Transport for Logging<T> {
+//!     fn execute<'a>(
+//!         &'a self,
+//!         client: &'a reqwest::Client,
+//!         request: reqwest::Request,
+//!     ) -> TransportFuture<'a> {
+//!         println!("{} {}", request.method(), request.url());
+//!         self.0.execute(client, request)
+//!     }
+//! }
+//!
+//! let session = Session::new().set_transport(Logging(ReqwestTransport));
+//! ```
+
+use std::{fmt::Debug, future::Future, pin::Pin, sync::Arc};
+
+use reqwest::{Request, Response};
+
+use crate::{errors::session::SessionError, session::Session};
+
+/// [`Transport::execute`]返回的Future
+pub type TransportFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<Response, SessionError>> + Send + 'a>>;
+
+/// 发送HTTP请求的传输层
+///
+/// 该trait已为`Fn(reqwest::Request) -> Result<reqwest::Response, SessionError>`闭包实现，
+/// 闭包可通过`http::Response`构造响应，便于在内存中模拟服务端
+pub trait Transport: Send + Sync {
+    /// 发送请求，`client`为构建该请求的[`Client`](super::Client)内部的[`reqwest::Client`]
+    fn execute<'a>(&'a self, client: &'a reqwest::Client, request: Request) -> TransportFuture<'a>;
+}
+
+/// 使用[`reqwest::Client`]发送请求的默认传输层
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ReqwestTransport;
+
+impl Transport for ReqwestTransport {
+    fn execute<'a>(&'a self, client: &'a reqwest::Client, request: Request) -> TransportFuture<'a> {
+        Box::pin(async move { Ok(client.execute(request).await?) })
+    }
+}
+
+impl<F> Transport for F
+where
+    F: Fn(Request) -> Result<Response, SessionError> + Send + Sync,
+{
+    fn execute<'a>(&'a self, _: &'a reqwest::Client, request: Request) -> TransportFuture<'a> {
+        let response = self(request);
+        Box::pin(async move { response })
+    }
+}
+
+/// [`Session`]中保存的传输层，与会话的所有克隆共享
+#[derive(Clone)]
+pub(crate) struct SharedTransport(pub(crate) Arc<dyn Transport>);
+
+impl Debug for SharedTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Transport")
+    }
+}
+
+impl Default for SharedTransport {
+    fn default() -> Self {
+        SharedTransport(Arc::new(ReqwestTransport))
+    }
+}
+
+impl Session {
+    /// 设置会话发送请求使用的传输层
+    ///
+    /// 已设置录像时，录像仍然生效，录制模式下请求通过新的传输层发出
+    pub fn set_transport(self, transport: impl Transport + 'static) -> Self {
+        Session {
+            transport: SharedTransport(Arc::new(transport)),
+            ..self
+        }
+    }
+
+    /// 恢复使用默认的[`ReqwestTransport`]发送请求
+    pub fn clear_transport(self) -> Self {
+        Session {
+            transport: SharedTransport::default(),
+            ..self
+        }
+    }
+}
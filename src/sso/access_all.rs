@@ -0,0 +1,114 @@
+//! 登陆后并发获取全部服务的访问权限
+
+use crate::session::{Client, Session, access_info::Service};
+#[cfg(feature = "card")]
+use crate::{card::access_card, errors::card::CardResult};
+#[cfg(feature = "mycqu")]
+use crate::{errors::mycqu::MyCQUResult, mycqu::access_mycqu};
+
+/// [`access_all`]中各服务获取访问权限的结果
+#[derive(Debug)]
+pub struct AccessReport {
+    /// 获取教务网访问权限的结果
+    #[cfg(feature = "mycqu")]
+    pub mycqu: MyCQUResult<()>,
+    /// 获取校园卡访问权限的结果
+    #[cfg(feature = "card")]
+    pub card: CardResult<()>,
+}
+
+impl AccessReport {
+    /// 是否成功获取了`service`的访问权限
+    pub fn is_success(&self, service: Service) -> bool {
+        match service {
+            #[cfg(feature = "mycqu")]
+            Service::MyCQU => self.mycqu.is_ok(),
+            #[cfg(feature = "card")]
+            Service::Card => self.card.is_ok(),
+        }
+    }
+
+    /// 是否成功获取了全部服务的访问权限
+    pub fn all_succeeded(&self) -> bool {
+        Service::ALL.iter().all(|service| self.is_success(*service))
+    }
+
+    /// 成功获取访问权限的服务
+    pub fn succeeded(&self) -> Vec<Service> {
+        Service::ALL
+            .iter()
+            .copied()
+            .filter(|service| self.is_success(*service))
+            .collect()
+    }
+
+    /// 获取访问权限失败的服务
+    pub fn failed(&self) -> Vec<Service> {
+        Service::ALL
+            .iter()
+            .copied()
+            .filter(|service| !self.is_success(*service))
+            .collect()
+    }
+}
+
+/// 并发获取当前启用的feature支持的全部服务的访问权限
+///
+/// 某个服务获取失败不会影响其他服务，各服务的结果记录在返回的[`AccessReport`]中，
+/// 成功获取的访问信息会写入`session`中
+///
+/// # Examples
+/// ```rust, no_run
+/// # use rsmycqu::session::{Client, Session};
+/// # use rsmycqu::sso::{access_all, login};
+/// # async fn access() {
+/// let client = Client::default();
+/// let mut session = Session::new();
+/// login(&client, &mut session, "your_auth", "your_password", false).await.unwrap();
+/// let report = access_all(&client, &mut session).await;
+/// if !report.all_succeeded() {
+///     println!("{report:?}");
+/// }
+/// # }
+/// ```
+pub async fn access_all(client: &Client, session: &mut Session) -> AccessReport {
+    // 各服务在会话的克隆上并发获取访问权限，完成后将成功获取的访问信息写回`session`
+    #[cfg(feature = "mycqu")]
+    let mut mycqu_session = session.clone();
+    #[cfg(feature = "mycqu")]
+    let mycqu = access_mycqu(client, &mut mycqu_session);
+    #[cfg(not(feature = "mycqu"))]
+    let mycqu = async {};
+
+    #[cfg(feature = "card")]
+    let mut card_session = session.clone();
+    #[cfg(feature = "card")]
+    let card = access_card(client, &mut card_session);
+    #[cfg(not(feature = "card"))]
+    let card = async {};
+
+    // 未启用的服务对应的结果为`()`
+    #[cfg_attr(not(all(feature = "mycqu", feature = "card")), allow(unused_variables))]
+    let (mycqu, card) = tokio::join!(mycqu, card);
+
+    #[cfg(feature = "mycqu")]
+    if mycqu.is_ok() {
+        session.access_infos.get_mut().mycqu_access_info = mycqu_session
+            .access_infos
+            .get_mut()
+            .mycqu_access_info
+            .take();
+    }
+    #[cfg(feature = "card")]
+    if card.is_ok() {
+        session.access_infos.get_mut().card_access_info =
+            card_session.access_infos.get_mut().card_access_info.take();
+    }
+
+    AccessReport {
+        #[cfg(feature = "mycqu")]
+        mycqu,
+        #[cfg(feature = "card")]
+        card,
+    }
+}
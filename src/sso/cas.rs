@@ -0,0 +1,125 @@
+//! 将统一身份认证作为身份提供方接入自有服务的服务端工具
+//!
+//! 自有服务可以通过[`login_url`]将用户重定向至统一身份认证，
+//! 并在用户登陆后使用[`validate_ticket`]校验回调中携带的服务票据，获得登陆用户信息
+
+use std::collections::HashMap;
+
+use reqwest::Url;
+use scraper::{ElementRef, Html, Selector};
+
+use crate::{
+    errors::{
+        ApiError,
+        sso::{SSOError, SSOResult},
+    },
+    session::Client,
+    utils::consts::{SSO_LOGIN_PATH, SSO_SERVICE_VALIDATE_PATH},
+};
+
+/// 通过服务票据校验的用户
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CasUser {
+    /// 统一身份认证号
+    pub user: String,
+    /// 统一身份认证返回的用户属性
+    ///
+    /// 属性名为去除`cas:`前缀后的小写名称，同名属性的多个值按返回顺序保存
+    pub attributes: HashMap<String, Vec<String>>,
+}
+
+impl CasUser {
+    /// 获取属性名为`name`的第一个值
+    pub fn attribute(&self, name: impl AsRef<str>) -> Option<&str> {
+        self.attributes
+            .get(&name.as_ref().to_lowercase())
+            .and_then(|values| values.first())
+            .map(String::as_str)
+    }
+}
+
+/// 生成将用户重定向至统一身份认证登陆的链接，用户登陆后会携带服务票据跳转回`service`
+pub fn login_url(client: &Client, service: impl AsRef<str>) -> String {
+    with_service(client.endpoints().sso_url(SSO_LOGIN_PATH), service, None)
+}
+
+/// 校验统一身份认证签发给`service`的服务票据
+///
+/// 使用CAS 3.0协议的`p3/serviceValidate`接口，校验失败时抛出[`SSOError::TicketValidationError`]
+pub async fn validate_ticket(
+    client: &Client,
+    service: impl AsRef<str>,
+    ticket: impl AsRef<str>,
+) -> SSOResult<CasUser> {
+    let url = with_service(
+        client.endpoints().sso_url(SSO_SERVICE_VALIDATE_PATH),
+        service,
+        Some(ticket.as_ref()),
+    );
+    let res = client.get(url).send().await?;
+
+    parse_service_response(&res.text().await?)
+}
+
+fn with_service(base: String, service: impl AsRef<str>, ticket: Option<&str>) -> String {
+    let mut url = Url::parse(&base).expect("sso endpoint should be a valid url");
+    url.query_pairs_mut()
+        .append_pair("service", service.as_ref());
+    if let Some(ticket) = ticket {
+        url.query_pairs_mut().append_pair("ticket", ticket);
+    }
+
+    url.into()
+}
+
+#[inline]
+fn selector(selector: &str) -> Selector {
+    Selector::parse(selector).expect("static selector should be valid")
+}
+
+fn element_text(element: ElementRef) -> String {
+    element.text().collect::<String>().trim().to_string()
+}
+
+/// 解析`serviceValidate`接口返回的XML
+pub(super) fn parse_service_response(xml: &str) -> SSOResult<CasUser> {
+    let document = Html::parse_document(xml);
+
+    if let Some(failure) = document
+        .select(&selector(r"cas\:authenticationFailure"))
+        .next()
+    {
+        return Err(SSOError::TicketValidationError {
+            code: failure.value().attr("code").unwrap_or_default().to_string(),
+            msg: element_text(failure),
+        }
+        .into());
+    }
+
+    let success = document
+        .select(&selector(r"cas\:authenticationSuccess"))
+        .next()
+        .ok_or_else(|| ApiError::ModelParse {
+            msg: "Expected element \"cas:authenticationSuccess\" is missing".to_string(),
+            raw_response: xml.to_string(),
+        })?;
+    let user = success
+        .select(&selector(r"cas\:user"))
+        .next()
+        .map(element_text)
+        .ok_or_else(|| ApiError::ModelParse {
+            msg: "Expected element \"cas:user\" is missing".to_string(),
+            raw_response: xml.to_string(),
+        })?;
+
+    let mut attributes: HashMap<String, Vec<String>> = HashMap::new();
+    for attribute in success.select(&selector(r"cas\:attributes > *")) {
+        let name = attribute.value().name();
+        attributes
+            .entry(name.strip_prefix("cas:").unwrap_or(name).to_string())
+            .or_default()
+            .push(element_text(attribute));
+    }
+
+    Ok(CasUser { user, attributes })
+}
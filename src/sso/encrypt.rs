@@ -5,7 +5,7 @@ use crate::{
     utils::encrypt::*,
 };
 
-pub(super) fn encrypt_password(
+pub(crate) fn encrypt_password(
     page_crypto: impl AsRef<str>,
     password: impl AsRef<str>,
 ) -> SSOResult<String> {
@@ -1,6 +1,8 @@
 //! 重庆大学单点登录（SSO）相关模块
 
-use reqwest::{Response, StatusCode};
+use std::fmt::{Debug, Formatter};
+
+use reqwest::{Response, StatusCode, Url};
 use snafu::ensure;
 
 use crate::{
@@ -10,23 +12,32 @@ use crate::{
         sso::{SSOError, SSOResult},
     },
     session::{Client, Session},
-    sso::tools::{LoginPageResponse, get_login_request_data, launch_login_data},
+    sso::tools::{LoginPageResponse, captcha_challenge, get_login_request_data, submit_login},
     utils::{
-        consts::{SSO_LOGIN_URL, SSO_LOGOUT_URL},
+        consts::{SSO_LOGIN_PATH, SSO_LOGOUT_PATH},
         get_response_header,
+        page_parser::SSOLoginPageData,
     },
 };
 
-mod encrypt;
+#[cfg(any(feature = "mycqu", feature = "card"))]
+mod access_all;
+pub mod cas;
+pub(crate) mod encrypt;
+mod qr;
 mod tools;
 
+#[cfg(any(feature = "mycqu", feature = "card"))]
+pub use access_all::{AccessReport, access_all};
+pub use qr::{QrLogin, QrLoginStart, QrLoginStatus, poll_qr_login, start_qr_login};
+
 #[cfg(test)]
 mod tests;
 
 /// 退出账号登陆
 pub async fn logout(client: &Client, session: &mut Session) -> SSOResult<()> {
     session
-        .execute(client.get(SSO_LOGOUT_URL))
+        .execute(client.get(client.endpoints().sso_url(SSO_LOGOUT_PATH)))
         .await
         .map_err(|_| SSOError::LogoutError)?;
     session.is_login = false;
@@ -42,9 +53,61 @@ pub enum LoginResult {
 
     /// 账号或密码错误
     IncorrectLoginCredentials,
+
+    /// 统一身份认证要求输入验证码，识别验证码后应使用[`login_with_captcha`]继续登陆
+    CaptchaRequired(CaptchaChallenge),
+
+    /// 账号已被锁定
+    AccountLocked,
+
+    /// 密码已过期，需要修改密码后才能登陆
+    PasswordExpired,
+
+    /// 账号已被禁用
+    AccountDisabled,
+
+    /// 登陆尝试次数过多，需要稍后再试
+    TooManyAttempts,
+
+    /// 统一身份认证正在维护
+    ServiceMaintenance,
+
+    /// 无法识别的登陆失败
+    Failed {
+        /// 登陆页面给出的错误码
+        code: Option<String>,
+        /// 登陆页面给出的错误提示
+        msg: Option<String>,
+    },
+}
+
+/// 需要验证码时的登陆续接信息，包含验证码图片与本次登陆流程所需数据
+#[derive(Clone, Eq, PartialEq)]
+pub struct CaptchaChallenge {
+    image: Vec<u8>,
+    login_page_data: SSOLoginPageData,
+}
+
+impl Debug for CaptchaChallenge {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CaptchaChallenge")
+            .field("image", &format_args!("<{} bytes>", self.image.len()))
+            .field("login_page_data", &self.login_page_data)
+            .finish()
+    }
+}
+
+impl CaptchaChallenge {
+    /// 验证码图片的原始字节
+    pub fn image(&self) -> &[u8] {
+        &self.image
+    }
 }
 
 /// 登陆账号
+///
+/// 当统一身份认证要求验证码时返回[`LoginResult::CaptchaRequired`]，此时应识别其中的验证码图片，
+/// 并调用[`login_with_captcha`]完成登陆
 pub async fn login(
     client: &Client,
     session: &mut Session,
@@ -61,48 +124,126 @@ pub async fn login(
             Ok(LoginResult::Success)
         }
 
+        LoginPageResponse::LoginFailed { result } => Ok(result),
+
         LoginPageResponse::NormalLogin { login_page_data } => {
-            let login_data = launch_login_data(auth, password, &login_page_data)?;
-            let res = session
-                .execute(client.post(SSO_LOGIN_URL).form(&login_data))
-                .await?;
-
-            match res.status() {
-                StatusCode::FOUND => {
-                    let url =
-                        get_response_header(&res, "Location").ok_or(ApiError::location_error())?;
-                    session.execute(client.get(url)).await?;
-                    session.is_login = true;
-                    Ok(LoginResult::Success)
-                }
-                StatusCode::UNAUTHORIZED => Ok(LoginResult::IncorrectLoginCredentials),
-                other => Err(SSOError::UnknownSSOError {
-                    msg: format!("Unexpected status code: {}", other),
-                }
-                .into()),
+            if login_page_data.captcha_url.is_some() {
+                return captcha_challenge(client, session, login_page_data).await;
             }
+            submit_login(client, session, auth, password, &login_page_data, None).await
         }
     }
 }
 
-#[cfg(feature = "mycqu")]
+/// 提交验证码继续登陆账号
+///
+/// `challenge`为[`login`]返回的[`LoginResult::CaptchaRequired`]中的续接信息，`captcha`为识别出的验证码，
+/// 验证码错误时将再次返回[`LoginResult::CaptchaRequired`]
+pub async fn login_with_captcha(
+    client: &Client,
+    session: &mut Session,
+    challenge: CaptchaChallenge,
+    auth: impl AsRef<str>,
+    password: impl AsRef<str>,
+    captcha: impl AsRef<str>,
+) -> SSOResult<LoginResult> {
+    submit_login(
+        client,
+        session,
+        auth,
+        password,
+        &challenge.login_page_data,
+        Some(captcha.as_ref()),
+    )
+    .await
+}
+
+/// 通过统一身份认证获取的服务许可
+#[derive(Debug)]
+pub struct ServiceAccess {
+    /// 服务票据（Service Ticket）
+    pub ticket: String,
+    /// 携带服务票据访问服务并跟随其跳转后得到的最终响应
+    pub response: Response,
+}
+
+/// 访问服务时最多跟随的跳转次数
+const MAX_SERVICE_REDIRECTS: usize = 10;
+
 /// 使用登陆了统一身份认证的账号获取指定服务许可
-pub(super) async fn access_services(
+///
+/// `service`为接入了统一身份认证的服务地址，该函数会完成统一身份认证的跳转流程，
+/// 并在携带票据访问服务后继续跟随服务的跳转，直至得到非跳转的响应，最多跟随10次跳转。
+/// 返回服务票据与该最终响应，可用于接入本库尚未支持的其他校内系统
+pub async fn access_service(
+    client: &Client,
+    session: &Session,
+    service: impl AsRef<str>,
+) -> SSOResult<ServiceAccess> {
+    ensure!(session.is_login, errors::NotLoginSnafu {});
+
+    let jump_url = service_jump_url(client, session, service.as_ref()).await?;
+    let jump_url = jump_url.as_str();
+    let ticket = service_ticket(jump_url).ok_or(ApiError::Website {
+        msg: format!("Expected service ticket in \"{jump_url}\" but not found"),
+    })?;
+
+    let mut url = Url::parse(jump_url).map_err(|err| ApiError::Website {
+        msg: format!("Invalid redirect location \"{jump_url}\": {err}"),
+    })?;
+    let mut response = session.execute(client.get(url.clone())).await?;
+    for _ in 0..MAX_SERVICE_REDIRECTS {
+        if !response.status().is_redirection() {
+            return Ok(ServiceAccess { ticket, response });
+        }
+
+        let location =
+            get_response_header(&response, "Location").ok_or(ApiError::location_error())?;
+        url = url.join(location).map_err(|err| ApiError::Website {
+            msg: format!("Invalid redirect location \"{location}\": {err}"),
+        })?;
+        response = session.execute(client.get(url.clone())).await?;
+    }
+
+    Err(ApiError::Website {
+        msg: format!("Too many redirects when accessing \"{}\"", service.as_ref()),
+    })
+}
+
+#[cfg(any(feature = "mycqu", feature = "card"))]
+/// 使用登陆了统一身份认证的账号获取指定服务许可，返回携带票据访问服务后的响应，不跟随服务的跳转
+pub(crate) async fn access_services(
     client: &Client,
     session: &Session,
     service: impl AsRef<str>,
 ) -> SSOResult<Response> {
+    let jump_url = service_jump_url(client, session, service.as_ref()).await?;
+
+    Ok(session.execute(client.get(jump_url)).await?)
+}
+
+/// 向统一身份认证请求服务许可，返回携带服务票据的跳转链接
+async fn service_jump_url(client: &Client, session: &Session, service: &str) -> SSOResult<String> {
     let res = session
         .execute(
             client
-                .get(SSO_LOGIN_URL)
-                .query(&[("service", service.as_ref())]),
+                .get(client.endpoints().sso_url(SSO_LOGIN_PATH))
+                .query(&[("service", service)]),
         )
         .await?;
 
     ensure!(res.status() == StatusCode::FOUND, errors::NotLoginSnafu {});
 
-    let jump_url = get_response_header(&res, "Location").ok_or(ApiError::location_error())?;
+    Ok(get_response_header(&res, "Location")
+        .ok_or(ApiError::location_error())?
+        .to_string())
+}
 
-    Ok(session.execute(client.get(jump_url)).await?)
+/// 从统一身份认证跳转链接中提取服务票据
+fn service_ticket(jump_url: &str) -> Option<String> {
+    Url::parse(jump_url)
+        .ok()?
+        .query_pairs()
+        .find(|(key, _)| key == "ticket")
+        .map(|(_, ticket)| ticket.into_owned())
 }
@@ -0,0 +1,220 @@
+//! 统一身份认证扫码登陆
+
+use std::fmt::{Debug, Formatter};
+
+use reqwest::StatusCode;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use crate::{
+    errors::{
+        ApiError,
+        sso::{SSOError, SSOResult},
+    },
+    session::{Client, Session},
+    sso::{
+        LoginResult,
+        tools::{LoginPageResponse, follow_login_redirect, get_login_request_data},
+    },
+    utils::{
+        consts::{SSO_LOGIN_PATH, SSO_QR_GENERATE_PATH, SSO_QR_IMAGE_PATH, SSO_QR_STATUS_PATH},
+        page_parser::SSOLoginPageData,
+        response_json_map,
+    },
+};
+
+/// 扫码登陆的二维码信息，用于展示二维码并轮询扫码状态
+#[derive(Clone, Eq, PartialEq)]
+pub struct QrLogin {
+    qr_id: String,
+    content: String,
+    image: Vec<u8>,
+    login_page_data: SSOLoginPageData,
+}
+
+impl Debug for QrLogin {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("QrLogin")
+            .field("qr_id", &self.qr_id)
+            .field("content", &self.content)
+            .field("image", &format_args!("<{} bytes>", self.image.len()))
+            .finish()
+    }
+}
+
+impl QrLogin {
+    /// 二维码ID
+    pub fn qr_id(&self) -> &str {
+        &self.qr_id
+    }
+
+    /// 二维码中编码的内容，可用于自行生成二维码
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    /// 二维码图片的原始字节
+    pub fn image(&self) -> &[u8] {
+        &self.image
+    }
+}
+
+/// 开始扫码登陆的结果
+#[derive(Debug, Eq, PartialEq)]
+pub enum QrLoginStart {
+    /// 等待扫码
+    Pending(QrLogin),
+
+    /// 无需扫码即已得到登陆结果，如会话已经登陆时为[`LoginResult::Success`]
+    Finished(LoginResult),
+}
+
+/// 二维码扫码状态
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum QrLoginStatus {
+    /// 等待扫码
+    Waiting,
+
+    /// 已扫码，等待在手机上确认
+    Scanned,
+
+    /// 已确认，此时会话已完成登陆
+    Confirmed,
+
+    /// 二维码已过期，需要重新调用[`start_qr_login`]
+    Expired,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct QrCodeInfo {
+    pub(super) qrcode_id: String,
+    pub(super) content: String,
+}
+
+/// 开始扫码登陆，获取需要展示给用户的二维码
+///
+/// 获取二维码后应定期调用[`poll_qr_login`]查询扫码状态，直至返回[`QrLoginStatus::Confirmed`]
+/// 或[`QrLoginStatus::Expired`]
+pub async fn start_qr_login(
+    client: &Client,
+    session: &mut Session,
+    force_relogin: bool,
+) -> SSOResult<QrLoginStart> {
+    let login_page_data = match get_login_request_data(client, session, force_relogin).await? {
+        LoginPageResponse::HasLogin { login_url } => {
+            session.execute(client.get(login_url)).await?;
+            session.is_login = true;
+            return Ok(QrLoginStart::Finished(LoginResult::Success));
+        }
+        LoginPageResponse::LoginFailed { result } => return Ok(QrLoginStart::Finished(result)),
+        LoginPageResponse::NormalLogin { login_page_data } => login_page_data,
+    };
+
+    let res = session
+        .execute(client.get(client.endpoints().sso_url(SSO_QR_GENERATE_PATH)))
+        .await?;
+    let raw_response = res.text().await?;
+    let info = parse_qr_code_info(&raw_response)?;
+
+    let res = session
+        .execute(
+            client
+                .get(client.endpoints().sso_url(SSO_QR_IMAGE_PATH))
+                .query(&[("qrcodeId", &info.qrcode_id)]),
+        )
+        .await?;
+    if res.status() != StatusCode::OK {
+        return Err(SSOError::UnknownSSOError {
+            msg: format!(
+                "Unexpected status code {} when fetching qr code",
+                res.status()
+            ),
+        }
+        .into());
+    }
+
+    Ok(QrLoginStart::Pending(QrLogin {
+        qr_id: info.qrcode_id,
+        content: info.content,
+        image: res.bytes().await?.to_vec(),
+        login_page_data,
+    }))
+}
+
+/// 查询扫码状态，在用户确认后完成登陆
+///
+/// 返回[`QrLoginStatus::Confirmed`]时会话已完成登陆，效果与[`login`](super::login)成功相同
+pub async fn poll_qr_login(
+    client: &Client,
+    session: &mut Session,
+    qr_login: &QrLogin,
+) -> SSOResult<QrLoginStatus> {
+    let res = session
+        .execute(
+            client
+                .get(client.endpoints().sso_url(SSO_QR_STATUS_PATH))
+                .query(&[("qrcodeId", &qr_login.qr_id)]),
+        )
+        .await?;
+    let (json, raw_response) = response_json_map(res).await?;
+
+    let status = parse_qr_status(&json, &raw_response)?;
+    if status != QrLoginStatus::Confirmed {
+        return Ok(status);
+    }
+
+    let res = session
+        .execute(
+            client
+                .post(client.endpoints().sso_url(SSO_LOGIN_PATH))
+                .form(&[
+                    ("type", "qrcode"),
+                    ("_eventId", "submit"),
+                    ("geolocation", ""),
+                    ("execution", &qr_login.login_page_data.login_page_flowkey),
+                    ("qrcodeId", &qr_login.qr_id),
+                ]),
+        )
+        .await?;
+
+    match res.status() {
+        StatusCode::FOUND => {
+            follow_login_redirect(client, session, &res).await?;
+            Ok(QrLoginStatus::Confirmed)
+        }
+        other => Err(SSOError::UnknownSSOError {
+            msg: format!("Unexpected status code {} when finishing qr login", other),
+        }
+        .into()),
+    }
+}
+
+pub(super) fn parse_qr_code_info(raw_response: &str) -> SSOResult<QrCodeInfo> {
+    serde_json::from_str(raw_response).map_err(|e| ApiError::ModelParse {
+        msg: format!("Model parse error: {e:?}"),
+        raw_response: raw_response.to_string(),
+    })
+}
+
+pub(super) fn parse_qr_status(
+    json: &Map<String, Value>,
+    raw_response: &str,
+) -> SSOResult<QrLoginStatus> {
+    let status = match json.get("status") {
+        Some(Value::String(status)) => status.parse().ok(),
+        Some(status) => status.as_u64(),
+        None => None,
+    };
+
+    match status {
+        Some(0) => Ok(QrLoginStatus::Waiting),
+        Some(1) => Ok(QrLoginStatus::Scanned),
+        Some(2) => Ok(QrLoginStatus::Confirmed),
+        Some(3) => Ok(QrLoginStatus::Expired),
+        _ => Err(ApiError::ModelParse {
+            msg: "Expected field \"status\" is missing or format incorrect".to_string(),
+            raw_response: raw_response.to_string(),
+        }),
+    }
+}
@@ -1,9 +1,22 @@
+use reqwest::{Method, Request, Response};
 use rstest::*;
 
 use crate::{
-    session::Session,
-    sso::{LoginResult, encrypt::encrypt_password, login},
-    utils::test_fixture::{LoginData, login_data, shared_client},
+    errors::{ApiError, session::SessionError, sso::SSOError},
+    session::{Client, Endpoints, Session},
+    sso::{
+        LoginResult, QrLoginStatus, access_service,
+        cas::{login_url, parse_service_response},
+        encrypt::encrypt_password,
+        login,
+        qr::{parse_qr_code_info, parse_qr_status},
+        service_ticket,
+        tools::{classify_login_error, launch_login_data, resolve_captcha_url},
+    },
+    utils::{
+        page_parser::{SSOLoginError, sso_login_error_parser, sso_login_parser},
+        test_fixture::{LoginData, login_data, shared_client},
+    },
 };
 
 #[rstest]
@@ -13,6 +26,282 @@ fn test_login_page_encrypt() {
     assert_eq!(encrypted_password.unwrap(), "04fgkn+qsF1CZTXR/K/mbQ==")
 }
 
+#[rstest]
+fn test_launch_login_data_with_captcha() {
+    let login_page_data = sso_login_parser(include_str!(
+        "../utils/page_parser/tests/sso_captcha_login_page.html"
+    ))
+    .unwrap();
+    let login_data =
+        launch_login_data("auth", "abc123456", &login_page_data, Some("a1b2")).unwrap();
+
+    assert!(login_data.contains(&("password", "04fgkn+qsF1CZTXR/K/mbQ==".to_string())));
+    assert!(login_data.contains(&("captcha", "a1b2".to_string())));
+
+    let login_data = launch_login_data("auth", "abc123456", &login_page_data, None).unwrap();
+    assert!(login_data.iter().all(|(key, _)| *key != "captcha"));
+}
+
+#[rstest]
+#[case(
+    "/api/captcha/generate/DEFAULT?timestamp=1",
+    "http://127.0.0.1:8080/api/captcha/generate/DEFAULT?timestamp=1"
+)]
+#[case(
+    "captcha/generate/DEFAULT",
+    "http://127.0.0.1:8080/authserver/captcha/generate/DEFAULT"
+)]
+#[case(
+    "https://captcha.cqu.edu.cn/generate",
+    "https://captcha.cqu.edu.cn/generate"
+)]
+#[case("//captcha.cqu.edu.cn/generate", "http://captcha.cqu.edu.cn/generate")]
+#[case("httpcaptcha", "http://127.0.0.1:8080/authserver/httpcaptcha")]
+fn test_resolve_captcha_url(#[case] captcha_url: &str, #[case] expected: &str) {
+    let client =
+        Client::default().set_endpoints(Endpoints::with_root("http://127.0.0.1:8080/authserver"));
+    assert_eq!(
+        resolve_captcha_url(&client, captcha_url).unwrap().as_str(),
+        expected
+    );
+}
+
+#[rstest]
+fn test_resolve_captcha_url_invalid_root() {
+    let client = Client::default().set_endpoints(Endpoints::with_root("not a url"));
+    assert!(matches!(
+        resolve_captcha_url(&client, "captcha/generate/DEFAULT"),
+        Err(ApiError::Inner {
+            source: SSOError::UnknownSSOError { .. }
+        })
+    ));
+}
+
+#[rstest]
+#[case("账号已被锁定，请30分钟后再试", LoginResult::AccountLocked)]
+#[case("密码已过期，请修改密码后登录", LoginResult::PasswordExpired)]
+#[case("该账号已被禁用", LoginResult::AccountDisabled)]
+#[case("登录失败次数过多", LoginResult::TooManyAttempts)]
+#[case("系统维护中", LoginResult::ServiceMaintenance)]
+#[case("用户名或密码错误", LoginResult::IncorrectLoginCredentials)]
+#[case(
+    "用户名或密码错误，忘记密码请重置密码",
+    LoginResult::IncorrectLoginCredentials
+)]
+#[case("密码错误，剩余尝试次数3次", LoginResult::IncorrectLoginCredentials)]
+#[case(
+    "用户名或密码错误，连续错误5次账号将被锁定",
+    LoginResult::IncorrectLoginCredentials
+)]
+#[case(
+    "密码错误次数过多，账号已被锁定，请重置密码",
+    LoginResult::AccountLocked
+)]
+#[case("账号已被禁用，请联系管理员重置密码", LoginResult::AccountDisabled)]
+#[case("密码已过期，请重置密码", LoginResult::PasswordExpired)]
+#[case("登录失败次数过多，请稍后再试", LoginResult::TooManyAttempts)]
+#[case("Your account is locked", LoginResult::AccountLocked)]
+#[case(
+    "Incorrect username or password",
+    LoginResult::IncorrectLoginCredentials
+)]
+fn test_classify_login_error(#[case] msg: &str, #[case] expected: LoginResult) {
+    let error = SSOLoginError {
+        code: None,
+        msg: Some(msg.to_string()),
+    };
+    assert_eq!(classify_login_error(error), expected);
+}
+
+fn fake_sso(request: Request) -> Result<Response, SessionError> {
+    let response = match *request.method() {
+        Method::GET => http::Response::builder().body(include_str!(
+            "../utils/page_parser/tests/sso_login_page.html"
+        )),
+        _ => http::Response::builder().status(401).body(
+            r#"<html><body><p id="login-error-code">1410041</p><span class="login_auth_error" id="msg">未知错误</span></body></html>"#,
+        ),
+    };
+
+    Ok(response.unwrap().into())
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_login_unknown_error_passed_through() {
+    let mut session = Session::new().set_transport(fake_sso);
+    let result = login(&Client::default(), &mut session, "auth", "password", false)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        result,
+        LoginResult::Failed {
+            code: Some("1410041".to_string()),
+            msg: Some("未知错误".to_string()),
+        }
+    );
+    assert!(!session.is_login());
+}
+
+#[rstest]
+fn test_classify_unknown_login_error() {
+    let error =
+        sso_login_error_parser(r#"<html><body><p id="login-error-code">1410041</p></body></html>"#)
+            .unwrap();
+    assert_eq!(
+        classify_login_error(error),
+        LoginResult::Failed {
+            code: Some("1410041".to_string()),
+            msg: None,
+        }
+    );
+}
+
+#[rstest]
+#[case(r#"{"status": 0}"#, QrLoginStatus::Waiting)]
+#[case(r#"{"status": "1"}"#, QrLoginStatus::Scanned)]
+#[case(r#"{"status": 2}"#, QrLoginStatus::Confirmed)]
+#[case(r#"{"status": "3"}"#, QrLoginStatus::Expired)]
+fn test_parse_qr_status(#[case] raw_response: &str, #[case] expected: QrLoginStatus) {
+    let json = serde_json::from_str(raw_response).unwrap();
+    assert_eq!(parse_qr_status(&json, raw_response).unwrap(), expected);
+}
+
+#[rstest]
+fn test_parse_invalid_qr_status() {
+    let raw_response = r#"{"status": 9}"#;
+    let json = serde_json::from_str(raw_response).unwrap();
+    assert!(matches!(
+        parse_qr_status(&json, raw_response),
+        Err(ApiError::ModelParse { .. })
+    ));
+}
+
+#[rstest]
+fn test_parse_qr_code_info() {
+    let info = parse_qr_code_info(
+        r#"{"qrcodeId": "d2f1c3", "content": "https://sso.cqu.edu.cn/qr?id=d2f1c3"}"#,
+    )
+    .unwrap();
+    assert_eq!(info.qrcode_id, "d2f1c3");
+    assert_eq!(info.content, "https://sso.cqu.edu.cn/qr?id=d2f1c3");
+}
+
+#[rstest]
+#[case(
+    "https://my.cqu.edu.cn/authserver/login?ticket=ST-123-abc",
+    Some("ST-123-abc")
+)]
+#[case(
+    "https://card.cqu.edu.cn:7280/ias/prelogin?sysid=FWDT&ticket=ST-4%2B5",
+    Some("ST-4+5")
+)]
+#[case("https://my.cqu.edu.cn/authserver/login", None)]
+fn test_service_ticket(#[case] jump_url: &str, #[case] expected: Option<&str>) {
+    assert_eq!(service_ticket(jump_url).as_deref(), expected);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_access_service_before_login(shared_client: &'static crate::session::Client) {
+    let session = Session::new();
+    let res = access_service(shared_client, &session, "https://my.cqu.edu.cn/").await;
+    assert!(matches!(res.unwrap_err(), ApiError::NotLogin));
+}
+
+fn redirect(location: &str) -> Result<Response, SessionError> {
+    Ok(http::Response::builder()
+        .status(302)
+        .header("Location", location)
+        .body("")
+        .unwrap()
+        .into())
+}
+
+fn fake_service(request: Request) -> Result<Response, SessionError> {
+    match request.url().path() {
+        "/login" => {
+            let (_, service) = request
+                .url()
+                .query_pairs()
+                .find(|(key, _)| key == "service")
+                .unwrap();
+            redirect(&format!("{service}?ticket=ST-1"))
+        }
+        "/prelogin" => redirect("/ssoticket?id=1"),
+        "/ssoticket" => redirect("https://app.example.com/home"),
+        "/home" => Ok(http::Response::builder().body("home").unwrap().into()),
+        _ => redirect("/loop"),
+    }
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_access_service_follows_redirects() {
+    let client = Client::default().set_endpoints(Endpoints::with_root("http://127.0.0.1:8080"));
+    let mut session = Session::new().set_transport(fake_service);
+    session.is_login = true;
+
+    let access = access_service(&client, &session, "https://app.example.com/prelogin")
+        .await
+        .unwrap();
+    assert_eq!(access.ticket, "ST-1");
+    assert_eq!(access.response.text().await.unwrap(), "home");
+
+    let res = access_service(&client, &session, "https://app.example.com/loop").await;
+    assert!(matches!(res.unwrap_err(), ApiError::Website { .. }));
+}
+
+#[rstest]
+fn test_cas_login_url() {
+    let client = Client::default().set_endpoints(Endpoints::with_root("http://127.0.0.1:8080"));
+    assert_eq!(
+        login_url(&client, "https://app.example.com/callback?next=/home"),
+        "http://127.0.0.1:8080/login?service=https%3A%2F%2Fapp.example.com%2Fcallback%3Fnext%3D%2Fhome"
+    );
+}
+
+#[rstest]
+fn test_parse_cas_success_response() {
+    let user = parse_service_response(
+        r#"<cas:serviceResponse xmlns:cas="http://www.yale.edu/tp/cas">
+    <cas:authenticationSuccess>
+        <cas:user>20200001</cas:user>
+        <cas:attributes>
+            <cas:userName>张三</cas:userName>
+            <cas:memberOf>student</cas:memberOf>
+            <cas:memberOf>undergraduate</cas:memberOf>
+        </cas:attributes>
+    </cas:authenticationSuccess>
+</cas:serviceResponse>"#,
+    )
+    .unwrap();
+
+    assert_eq!(user.user, "20200001");
+    assert_eq!(user.attribute("userName"), Some("张三"));
+    assert_eq!(
+        user.attributes["memberof"],
+        vec!["student", "undergraduate"]
+    );
+}
+
+#[rstest]
+fn test_parse_cas_failure_response() {
+    let res = parse_service_response(
+        r#"<cas:serviceResponse xmlns:cas="http://www.yale.edu/tp/cas">
+    <cas:authenticationFailure code="INVALID_TICKET">Ticket ST-1856339 not recognized</cas:authenticationFailure>
+</cas:serviceResponse>"#,
+    );
+
+    assert!(matches!(
+        res.unwrap_err(),
+        ApiError::Inner {
+            source: SSOError::TicketValidationError { code, msg }
+        } if code == "INVALID_TICKET" && msg == "Ticket ST-1856339 not recognized"
+    ));
+}
+
 #[rstest]
 #[ignore]
 #[tokio::test]
@@ -1,4 +1,4 @@
-use reqwest::{Response, StatusCode};
+use reqwest::{Response, StatusCode, Url};
 
 use crate::{
     errors::{
@@ -6,11 +6,11 @@ use crate::{
         sso::{SSOError, SSOResult},
     },
     session::{Client, Session},
-    sso::{encrypt::encrypt_password, logout},
+    sso::{CaptchaChallenge, LoginResult, encrypt::encrypt_password, logout},
     utils::{
-        consts::SSO_LOGIN_URL,
+        consts::{SSO_CAPTCHA_FIELD, SSO_LOGIN_PATH},
         get_response_header,
-        page_parser::{SSOLoginPageData, sso_login_parser},
+        page_parser::{SSOLoginError, SSOLoginPageData, sso_login_error_parser, sso_login_parser},
     },
 };
 
@@ -18,6 +18,7 @@ use crate::{
 pub(super) enum LoginPageResponse {
     HasLogin { login_url: String },
     NormalLogin { login_page_data: SSOLoginPageData },
+    LoginFailed { result: LoginResult },
 }
 
 async fn launch_normal_login_result(res: Response) -> SSOResult<LoginPageResponse> {
@@ -34,12 +35,13 @@ pub(super) async fn get_login_request_data(
     session: &mut Session,
     force_relogin: bool,
 ) -> SSOResult<LoginPageResponse> {
-    let res = session.execute(client.get(SSO_LOGIN_URL)).await?;
+    let login_url = client.endpoints().sso_url(SSO_LOGIN_PATH);
+    let res = session.execute(client.get(&login_url)).await?;
     match res.status() {
         StatusCode::FOUND => {
             if force_relogin {
                 logout(client, session).await?;
-                let local_res = session.execute(client.get(SSO_LOGIN_URL)).await?;
+                let local_res = session.execute(client.get(&login_url)).await?;
                 return launch_normal_login_result(local_res).await;
             }
 
@@ -55,15 +57,77 @@ pub(super) async fn get_login_request_data(
             })
         }
         StatusCode::OK => launch_normal_login_result(res).await,
-        other => Err(SSOError::UnknownSSOError {
-            msg: format!(
-                "status code {} is got (302 expected) when sending login post, \
-            but can not find the element span.login_auth_error#msg",
-                other
-            )
-            .to_string(),
+        other => match classify_error_response(other, &res.text().await?) {
+            Some(result) => Ok(LoginPageResponse::LoginFailed { result }),
+            None => Err(SSOError::UnknownSSOError {
+                msg: format!(
+                    "status code {} is got (302 expected) when sending login post, \
+                but can not find the element span.login_auth_error#msg",
+                    other
+                )
+                .to_string(),
+            }
+            .into()),
+        },
+    }
+}
+
+/// 根据登陆页面中的错误提示与错误码判断登陆失败原因
+///
+/// 错误提示中常附带“请重置密码”“连续错误5次将被锁定”等建议，因此只匹配描述账号当前状态的短语，
+/// 并按账号状态、尝试次数、服务维护、账号密码错误的顺序判断
+pub(super) fn classify_login_error(error: SSOLoginError) -> LoginResult {
+    let msg = error.msg.as_deref().unwrap_or_default().to_lowercase();
+    let contains_any = |keywords: &[&str]| keywords.iter().any(|keyword| msg.contains(keyword));
+
+    if contains_any(&[
+        "已锁定",
+        "已被锁定",
+        "已冻结",
+        "已被冻结",
+        "is locked",
+        "been locked",
+    ]) {
+        LoginResult::AccountLocked
+    } else if contains_any(&[
+        "已禁用",
+        "已被禁用",
+        "已停用",
+        "已被停用",
+        "已注销",
+        "is disabled",
+        "been disabled",
+    ]) {
+        LoginResult::AccountDisabled
+    } else if contains_any(&[
+        "密码已过期",
+        "密码过期",
+        "密码已失效",
+        "password expired",
+        "has expired",
+    ]) {
+        LoginResult::PasswordExpired
+    } else if contains_any(&["次数过多", "过于频繁", "操作频繁", "too many"]) {
+        LoginResult::TooManyAttempts
+    } else if contains_any(&["维护", "maintenance"]) {
+        LoginResult::ServiceMaintenance
+    } else if contains_any(&["密码错误", "用户名或密码", "账号或密码", "incorrect"])
+    {
+        LoginResult::IncorrectLoginCredentials
+    } else {
+        LoginResult::Failed {
+            code: error.code,
+            msg: error.msg,
         }
-        .into()),
+    }
+}
+
+/// 解析非预期状态码的登陆响应，无法判断失败原因时返回`None`
+fn classify_error_response(status: StatusCode, html: &str) -> Option<LoginResult> {
+    match sso_login_error_parser(html) {
+        Some(error) => Some(classify_login_error(error)),
+        None if status == StatusCode::SERVICE_UNAVAILABLE => Some(LoginResult::ServiceMaintenance),
+        None => None,
     }
 }
 
@@ -72,8 +136,9 @@ pub(super) fn launch_login_data(
     username: impl AsRef<str>,
     password: impl AsRef<str>,
     login_page_data: &SSOLoginPageData,
-) -> SSOResult<[(&'static str, String); 7]> {
-    Ok([
+    captcha: Option<&str>,
+) -> SSOResult<Vec<(&'static str, String)>> {
+    let mut data = vec![
         ("username", username.as_ref().to_owned()),
         ("type", "UsernamePassword".to_string()),
         ("_eventId", "submit".to_string()),
@@ -84,5 +149,117 @@ pub(super) fn launch_login_data(
             "password",
             encrypt_password(&login_page_data.login_croypto, password)?,
         ),
-    ])
+    ];
+    if let Some(captcha) = captcha {
+        data.push((SSO_CAPTCHA_FIELD, captcha.to_string()));
+    }
+
+    Ok(data)
+}
+
+/// 提交登陆表单并解析登陆结果
+pub(super) async fn submit_login(
+    client: &Client,
+    session: &mut Session,
+    auth: impl AsRef<str>,
+    password: impl AsRef<str>,
+    login_page_data: &SSOLoginPageData,
+    captcha: Option<&str>,
+) -> SSOResult<LoginResult> {
+    let login_data = launch_login_data(auth, password, login_page_data, captcha)?;
+    let res = session
+        .execute(
+            client
+                .post(client.endpoints().sso_url(SSO_LOGIN_PATH))
+                .form(&login_data),
+        )
+        .await?;
+
+    match res.status() {
+        StatusCode::FOUND => follow_login_redirect(client, session, &res).await,
+        StatusCode::UNAUTHORIZED => {
+            let html = res.text().await?;
+            match sso_login_error_parser(&html).map(classify_login_error) {
+                Some(LoginResult::IncorrectLoginCredentials) | None => {
+                    // 多次登陆失败后，统一身份认证会在返回的登陆页面中要求输入验证码
+                    match sso_login_parser(&html) {
+                        Some(login_page_data) if login_page_data.captcha_url.is_some() => {
+                            captcha_challenge(client, session, login_page_data).await
+                        }
+                        _ => Ok(LoginResult::IncorrectLoginCredentials),
+                    }
+                }
+                Some(result) => Ok(result),
+            }
+        }
+        other => match classify_error_response(other, &res.text().await?) {
+            Some(result) => Ok(result),
+            None => Err(SSOError::UnknownSSOError {
+                msg: format!("Unexpected status code: {}", other),
+            }
+            .into()),
+        },
+    }
+}
+
+/// 跟随登陆成功后的跳转，完成登陆
+pub(super) async fn follow_login_redirect(
+    client: &Client,
+    session: &mut Session,
+    res: &Response,
+) -> SSOResult<LoginResult> {
+    let url = get_response_header(res, "Location").ok_or(ApiError::location_error())?;
+    session.execute(client.get(url)).await?;
+    session.is_login = true;
+    Ok(LoginResult::Success)
+}
+
+/// 获取验证码图片，生成需要验证码的登陆结果
+pub(super) async fn captcha_challenge(
+    client: &Client,
+    session: &Session,
+    login_page_data: SSOLoginPageData,
+) -> SSOResult<LoginResult> {
+    let captcha_url = resolve_captcha_url(
+        client,
+        login_page_data
+            .captcha_url
+            .as_deref()
+            .expect("captcha_url should be checked before requesting captcha"),
+    )?;
+
+    let res = session.execute(client.get(captcha_url)).await?;
+    if res.status() != StatusCode::OK {
+        return Err(SSOError::UnknownSSOError {
+            msg: format!(
+                "Unexpected status code {} when fetching captcha",
+                res.status()
+            ),
+        }
+        .into());
+    }
+
+    Ok(LoginResult::CaptchaRequired(CaptchaChallenge {
+        image: res.bytes().await?.to_vec(),
+        login_page_data,
+    }))
+}
+
+/// 以登陆页面的地址为基准解析验证码地址，验证码地址可以是绝对地址或相对地址
+pub(super) fn resolve_captcha_url(client: &Client, captcha_url: &str) -> SSOResult<Url> {
+    let login_url = client.endpoints().sso_url(SSO_LOGIN_PATH);
+    Url::parse(&login_url)
+        .map_err(|err| -> ApiError<SSOError> {
+            SSOError::UnknownSSOError {
+                msg: format!("Invalid sso login url {login_url}: {err}"),
+            }
+            .into()
+        })?
+        .join(captcha_url)
+        .map_err(|err| {
+            SSOError::UnknownSSOError {
+                msg: format!("Invalid captcha url {captcha_url}: {err}"),
+            }
+            .into()
+        })
 }
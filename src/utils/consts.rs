@@ -1,103 +1,109 @@
-use const_format::formatcp;
+//! 各服务接口相对于对应根地址（见[`Endpoints`](crate::session::Endpoints)）的路径
 
 #[cfg(feature = "mycqu")]
-pub(crate) const MYCQU_ROOT_URL: &str = "https://my.cqu.edu.cn";
+pub(crate) const MYCQU_TOKEN_INDEX_PATH: &str = "/enroll/token-index";
+
 #[cfg(feature = "mycqu")]
-pub(crate) const MYCQU_TOKEN_INDEX_URL: &str = formatcp!("{MYCQU_ROOT_URL}/enroll/token-index");
+pub(crate) const MYCQU_TOKEN_PATH: &str = "/authserver/oauth/token";
 
 #[cfg(feature = "mycqu")]
-pub(crate) const MYCQU_TOKEN_URL: &str = formatcp!("{MYCQU_ROOT_URL}/authserver/oauth/token");
+pub(crate) const MYCQU_AUTHORIZE_PATH: &str = "/authserver/oauth/authorize";
 
 #[cfg(feature = "mycqu")]
-pub(crate) const MYCQU_AUTHORIZE_URL: &str = formatcp!(
-    "{MYCQU_ROOT_URL}/authserver/oauth/authorize?client_id=enroll-prod&response_type=code&scope=all&state=&redirect_uri={MYCQU_TOKEN_INDEX_URL}"
-);
+pub(crate) const MYCQU_SERVICE_PATH: &str = "/authserver/authentication/cas";
 
 #[cfg(feature = "mycqu")]
-pub(crate) const MYCQU_SERVICE_URL: &str =
-    formatcp!("{MYCQU_ROOT_URL}/authserver/authentication/cas");
+pub(crate) const MYCQU_API_SESSION_PATH: &str =
+    "/api/timetable/optionFinder/session?blankOption=false";
 
 #[cfg(feature = "mycqu")]
-pub(crate) const MYCQU_API_SESSION_URL: &str =
-    formatcp!("{MYCQU_ROOT_URL}/api/timetable/optionFinder/session?blankOption=false");
+pub(crate) const MYCQU_API_USER_PATH: &str = "/authserver/simple-user";
 
 #[cfg(feature = "mycqu")]
-pub(crate) const MYCQU_API_USER_URL: &str = formatcp!("{MYCQU_ROOT_URL}/authserver/simple-user");
+pub(crate) const MYCQU_API_ALL_SESSION_INFO_PATH: &str = "/api/resourceapi/session/list";
 
 #[cfg(feature = "mycqu")]
-pub(crate) const MYCQU_API_ALL_SESSION_INFO_URL: &str =
-    formatcp!("{MYCQU_ROOT_URL}/api/resourceapi/session/list");
+pub(crate) const MYCQU_API_CURR_SESSION_INFO_PATH: &str =
+    "/api/resourceapi/session/cur-active-session";
 
 #[cfg(feature = "mycqu")]
-pub(crate) const MYCQU_API_CURR_SESSION_INFO_URL: &str =
-    formatcp!("{MYCQU_ROOT_URL}/api/resourceapi/session/cur-active-session");
+pub(crate) const MYCQU_API_SESSION_INFO_DETAIL_PATH: &str = "/api/resourceapi/session/detail";
 
 #[cfg(feature = "mycqu")]
-pub(crate) const MYCQU_API_SESSION_INFO_DETAIL_URL: &str =
-    formatcp!("{MYCQU_ROOT_URL}/api/resourceapi/session/detail");
+pub(crate) const MYCQU_API_TIMETABLE_PATH: &str =
+    "/api/timetable/class/timetable/student/my-table-detail";
 
 #[cfg(feature = "mycqu")]
-pub(crate) const MYCQU_API_TIMETABLE_URL: &str =
-    formatcp!("{MYCQU_ROOT_URL}/api/timetable/class/timetable/student/my-table-detail");
+pub(crate) const MYCQU_API_ENROLL_TIMETABLE_PATH: &str = "/api/enrollment/timetable/student";
 
 #[cfg(feature = "mycqu")]
-pub(crate) const MYCQU_API_ENROLL_TIMETABLE_URL: &str =
-    formatcp!("{MYCQU_ROOT_URL}/api/enrollment/timetable/student");
+pub(crate) const MYCQU_API_SCORE_PATH: &str = "/api/sam/score/student/score";
 
 #[cfg(feature = "mycqu")]
-pub(crate) const MYCQU_API_SCORE_URL: &str =
-    formatcp!("{MYCQU_ROOT_URL}/api/sam/score/student/score");
+pub(crate) const MYCQU_API_SCORE_DETAIL_PATH: &str = "/api/sam/score/student/score-detail";
 
 #[cfg(feature = "mycqu")]
-pub(crate) const MYCQU_API_GPA_RANKING_URL: &str =
-    formatcp!("{MYCQU_ROOT_URL}/api/sam/score/student/studentGpaRanking");
+pub(crate) const MYCQU_API_GPA_RANKING_PATH: &str = "/api/sam/score/student/studentGpaRanking";
 
 #[cfg(feature = "mycqu")]
-pub(crate) const MYCQU_API_EXAM_LIST_URL: &str =
-    formatcp!("{MYCQU_ROOT_URL}/api/exam/examTask/get-student-exam-tab-list");
+pub(crate) const MYCQU_API_EXAM_LIST_PATH: &str = "/api/exam/examTask/get-student-exam-tab-list";
 
 #[cfg(feature = "mycqu")]
-pub(crate) const MYCQU_API_ENROLL_COURSE_LIST_URL: &str =
-    formatcp!("{MYCQU_ROOT_URL}/api/enrollment/enrollment/course-list");
+pub(crate) const MYCQU_API_ENROLL_COURSE_LIST_PATH: &str = "/api/enrollment/enrollment/course-list";
 
 #[cfg(feature = "mycqu")]
-pub(crate) const MYCQU_API_ENROLL_COURSE_DETAIL_URL: &str =
-    formatcp!("{MYCQU_ROOT_URL}/api/enrollment/enrollment/courseDetails");
+pub(crate) const MYCQU_API_ENROLL_COURSE_DETAIL_PATH: &str =
+    "/api/enrollment/enrollment/courseDetails";
+
+#[cfg(feature = "sso")]
+pub(crate) const SSO_LOGIN_PATH: &str = "/login";
+
+#[cfg(feature = "sso")]
+pub(crate) const SSO_LOGOUT_PATH: &str = "/logout";
+
+#[cfg(feature = "sso")]
+pub(crate) const SSO_SERVICE_VALIDATE_PATH: &str = "/p3/serviceValidate";
+
+#[cfg(feature = "sso")]
+pub(crate) const SSO_QR_GENERATE_PATH: &str = "/api/qrcode/generate";
 
 #[cfg(feature = "sso")]
-pub(crate) const SSO_ROOT_URL: &str = "https://sso.cqu.edu.cn";
+pub(crate) const SSO_QR_IMAGE_PATH: &str = "/api/qrcode/image";
 
 #[cfg(feature = "sso")]
-pub(crate) const SSO_LOGIN_URL: &str = formatcp!("{SSO_ROOT_URL}/login");
+pub(crate) const SSO_QR_STATUS_PATH: &str = "/api/qrcode/status";
 
+/// 登陆表单中验证码字段的名称
 #[cfg(feature = "sso")]
-pub(crate) const SSO_LOGOUT_URL: &str = formatcp!("{SSO_ROOT_URL}/logout");
+pub(crate) const SSO_CAPTCHA_FIELD: &str = "captcha";
 
+/// 位于[`Endpoints::card_ias`](crate::session::Endpoints::card_ias)下
 #[cfg(feature = "card")]
-pub(crate) const CARD_SERVICE_URL: &str = "http://card.cqu.edu.cn:7280/ias/prelogin?sysid=FWDT";
+pub(crate) const CARD_SERVICE_PATH: &str = "/ias/prelogin?sysid=FWDT";
 
 #[cfg(feature = "card")]
-pub(crate) const CARD_HALL_TICKET_URL: &str = "http://card.cqu.edu.cn/cassyno/index";
+pub(crate) const CARD_HALL_TICKET_PATH: &str = "/cassyno/index";
 
 #[cfg(feature = "card")]
-pub(crate) const CARD_PAGE_URL: &str = "http://card.cqu.edu.cn/Page/Page";
+pub(crate) const CARD_PAGE_PATH: &str = "/Page/Page";
 
+/// 位于[`Endpoints::card_blade`](crate::session::Endpoints::card_blade)下
 #[cfg(feature = "card")]
-pub(crate) const CARD_PAGE_TICKET_POST_FORM_URL: &str =
-    "http://card.cqu.edu.cn:8080/blade-auth/token/thirdToToken/fwdt";
+pub(crate) const CARD_PAGE_TICKET_POST_FORM_PATH: &str = "/blade-auth/token/thirdToToken/fwdt";
 
+/// 位于[`Endpoints::card_blade`](crate::session::Endpoints::card_blade)下
 #[cfg(feature = "card")]
-pub(crate) const CARD_BLADE_AUTH_URL: &str = "http://card.cqu.edu.cn:8080/blade-auth/token/fwdt";
+pub(crate) const CARD_BLADE_AUTH_PATH: &str = "/blade-auth/token/fwdt";
 
+/// 位于[`Endpoints::card_blade`](crate::session::Endpoints::card_blade)下
 #[cfg(feature = "card")]
-pub(crate) const CARD_GET_DORM_FEE_URL: &str =
-    "http://card.cqu.edu.cn:8080/charge/feeitem/getThirdData";
+pub(crate) const CARD_GET_DORM_FEE_PATH: &str = "/charge/feeitem/getThirdData";
 
 #[cfg(feature = "card")]
-pub(crate) const CARD_GET_CARD_URL: &str = "http://card.cqu.edu.cn/NcAccType/GetCurrentAccountList";
+pub(crate) const CARD_GET_CARD_PATH: &str = "/NcAccType/GetCurrentAccountList";
 
 #[cfg(feature = "card")]
-pub(crate) const CARD_GET_BILL_URL: &str = "http://card.cqu.edu.cn/NcReport/GetMyBill";
+pub(crate) const CARD_GET_BILL_PATH: &str = "/NcReport/GetMyBill";
 
 // #[cfg(feature = "library")]
 // pub(crate) const LIB_ROOT_URL: &str = "http://lib.cqu.edu.cn";
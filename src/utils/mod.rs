@@ -83,6 +83,8 @@ pub(crate) mod encrypt;
 #[cfg(feature = "sso")]
 pub(crate) mod page_parser;
 
+pub(crate) mod redact;
+
 #[cfg(test)]
 pub(crate) mod test_fixture;
 
@@ -8,10 +8,12 @@ fn selector(selector: &str) -> Selector {
     Selector::parse(selector).expect("static selector should be valid")
 }
 
-#[derive(Eq, PartialEq, Debug)]
+#[derive(Eq, PartialEq, Debug, Clone)]
 pub(crate) struct SSOLoginPageData {
     pub(crate) login_croypto: String,
     pub(crate) login_page_flowkey: String,
+    /// 需要验证码时验证码图片的链接，不需要时为`None`
+    pub(crate) captcha_url: Option<String>,
 }
 
 pub(crate) fn sso_login_parser(login_html: impl AsRef<str>) -> Option<SSOLoginPageData> {
@@ -28,9 +30,39 @@ pub(crate) fn sso_login_parser(login_html: impl AsRef<str>) -> Option<SSOLoginPa
             .next()?
             .text()
             .collect(),
+        captcha_url: document
+            .select(&selector("p#captcha-url"))
+            .next()
+            .map(|element| element.text().collect::<String>().trim().to_string())
+            .filter(|url| !url.is_empty()),
     })
 }
 
+/// 统一身份认证登陆页面中的错误信息
+#[derive(Eq, PartialEq, Debug, Clone, Default)]
+pub(crate) struct SSOLoginError {
+    /// 错误码，来自`p#login-error-code`
+    pub(crate) code: Option<String>,
+    /// 错误提示，来自`span.login_auth_error#msg`
+    pub(crate) msg: Option<String>,
+}
+
+pub(crate) fn sso_login_error_parser(login_html: impl AsRef<str>) -> Option<SSOLoginError> {
+    let document = Html::parse_document(login_html.as_ref());
+    let non_empty_text = |selector_str: &str| {
+        document
+            .select(&selector(selector_str))
+            .next()
+            .map(|element| element.text().collect::<String>().trim().to_string())
+            .filter(|text| !text.is_empty())
+    };
+
+    let code = non_empty_text("p#login-error-code");
+    let msg = non_empty_text("span.login_auth_error#msg");
+
+    (code.is_some() || msg.is_some()).then_some(SSOLoginError { code, msg })
+}
+
 #[cfg(feature = "card")]
 pub(crate) fn card_access_parser(html: impl AsRef<str>) -> Option<String> {
     let document = Html::parse_document(html.as_ref());
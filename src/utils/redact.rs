@@ -0,0 +1,153 @@
+//! 对请求与响应中的账号凭据、令牌等敏感信息脱敏
+
+use reqwest::{
+    Url,
+    header::{AUTHORIZATION, COOKIE, HeaderMap, HeaderName, LOCATION, SET_COOKIE},
+};
+use serde_json::Value;
+
+/// 敏感信息脱敏后的替代值
+pub(crate) const REDACTED: &str = "REDACTED";
+
+/// 需要脱敏的查询参数与表单字段
+const SENSITIVE_PARAMS: [&str; 9] = [
+    "password",
+    "croypto",
+    "ticket",
+    "ssoticketid",
+    "code",
+    "token",
+    "access_token",
+    "refresh_token",
+    "studentId",
+];
+
+/// 需要脱敏的JSON字段名所包含的关键字
+const SENSITIVE_JSON_KEYWORDS: [&str; 3] = ["token", "password", "secret"];
+
+fn is_sensitive_param(key: &str) -> bool {
+    SENSITIVE_PARAMS
+        .iter()
+        .any(|param| param.eq_ignore_ascii_case(key))
+}
+
+fn is_sensitive_header(name: &HeaderName) -> bool {
+    name == AUTHORIZATION
+        || name == COOKIE
+        || name == SET_COOKIE
+        || name.as_str().contains("auth")
+        || name.as_str().contains("token")
+}
+
+/// 对`key=value&...`形式的参数脱敏
+fn redact_pairs(pairs: url::form_urlencoded::Parse) -> String {
+    url::form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(pairs.map(|(key, value)| {
+            let value = if is_sensitive_param(&key) {
+                REDACTED.into()
+            } else {
+                value
+            };
+            (key, value)
+        }))
+        .finish()
+}
+
+/// 对链接中的敏感查询参数脱敏
+pub(crate) fn redact_url(url: &Url) -> String {
+    let mut url = url.clone();
+    if let Some(query) = url.query().map(ToString::to_string) {
+        url.set_query(Some(&redact_pairs(url::form_urlencoded::parse(
+            query.as_bytes(),
+        ))));
+    }
+
+    url.into()
+}
+
+/// 对请求头与响应头中的凭据脱敏，`Set-Cookie`仅脱敏Cookie值并保留其属性，`Location`仅脱敏敏感查询参数
+pub(crate) fn redact_headers(headers: &HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = value.to_str().unwrap_or_default();
+            let value = match name {
+                name if name == SET_COOKIE => redact_set_cookie(value),
+                name if name == LOCATION => redact_location(value),
+                name if is_sensitive_header(name) => REDACTED.to_string(),
+                _ => value.to_string(),
+            };
+            (name.to_string(), value)
+        })
+        .collect()
+}
+
+/// 重定向目标可能是相对链接，因此直接处理查询参数部分
+fn redact_location(location: &str) -> String {
+    match location.split_once('?') {
+        Some((path, query)) => format!(
+            "{path}?{}",
+            redact_pairs(url::form_urlencoded::parse(query.as_bytes()))
+        ),
+        None => location.to_string(),
+    }
+}
+
+fn redact_set_cookie(set_cookie: &str) -> String {
+    let (pair, attributes) = set_cookie
+        .split_once(';')
+        .map_or((set_cookie, None), |(pair, attributes)| {
+            (pair, Some(attributes))
+        });
+    let name = pair.split_once('=').map_or(pair, |(name, _)| name);
+
+    match attributes {
+        Some(attributes) => format!("{name}={REDACTED};{attributes}"),
+        None => format!("{name}={REDACTED}"),
+    }
+}
+
+/// 对请求体或响应体脱敏，支持JSON与`application/x-www-form-urlencoded`两种格式
+pub(crate) fn redact_body(body: &str) -> String {
+    if let Ok(mut json) = serde_json::from_str::<Value>(body) {
+        redact_json(&mut json);
+        return json.to_string();
+    }
+
+    if !body.is_empty()
+        && !body.contains(char::is_whitespace)
+        && body.contains('=')
+        && !body.contains('<')
+    {
+        return redact_pairs(url::form_urlencoded::parse(body.as_bytes()));
+    }
+
+    body.to_string()
+}
+
+fn redact_json(json: &mut Value) {
+    match json {
+        Value::Object(map) => map.iter_mut().for_each(|(key, value)| {
+            let key = key.to_lowercase();
+            if SENSITIVE_JSON_KEYWORDS
+                .iter()
+                .any(|keyword| key.contains(keyword))
+                && !value.is_object()
+                && !value.is_array()
+            {
+                *value = Value::String(REDACTED.to_string());
+            } else {
+                redact_json(value);
+            }
+        }),
+        Value::Array(array) => array.iter_mut().for_each(redact_json),
+        // 部分接口会将JSON以字符串形式再次编码
+        Value::String(text) if text.starts_with('{') => {
+            if let Ok(mut inner) = serde_json::from_str::<Value>(text) {
+                redact_json(&mut inner);
+                *text = inner.to_string();
+            }
+        }
+        _ => {}
+    }
+}
@@ -8,10 +8,17 @@ use rstest::*;
 
 #[cfg(feature = "card")]
 use crate::card::access_card;
+#[cfg(feature = "card")]
+use crate::session::access_info::CardAccessInfo;
 use crate::{
     mycqu::access_mycqu,
-    session::{Client, Session},
+    session::{
+        Client, Session,
+        access_info::{AccessInfos, MyCQUAccessInfo},
+        cassette::{Cassette, CassetteMode},
+    },
     sso::{LoginResult, login},
+    utils::redact::REDACTED,
 };
 
 #[derive(Debug, Eq, PartialEq)]
@@ -154,3 +161,20 @@ pub(crate) async fn access_card_session(
         Some(session) => session.clone(),
     }
 }
+
+/// 使用录像回放请求的会话，该会话视为已登陆并已获取全部服务的访问权限
+pub(crate) fn replay_session(cassette: &str) -> Session {
+    let mut session = Session::new()
+        .set_access_infos(AccessInfos {
+            mycqu_access_info: Some(MyCQUAccessInfo::new(REDACTED)),
+            #[cfg(feature = "card")]
+            card_access_info: Some(CardAccessInfo::new()),
+        })
+        .set_cassette(
+            Cassette::from_json(cassette).expect("cassette should be valid json"),
+            CassetteMode::Replay,
+        );
+    session.is_login = true;
+
+    session
+}